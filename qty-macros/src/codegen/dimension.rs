@@ -0,0 +1,167 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::codegen::quantity::check_formulas;
+use crate::parsing::*;
+
+// Returns the type-level integer representing `exp`.
+fn codegen_int_type(exp: i32) -> TokenStream {
+    match exp {
+        0 => quote!(qty_dimension::Z0),
+        exp if exp > 0 => {
+            let inner = codegen_int_type(exp - 1);
+            quote!(qty_dimension::P<#inner>)
+        }
+        exp => {
+            let inner = codegen_int_type(exp + 1);
+            quote!(qty_dimension::N<#inner>)
+        }
+    }
+}
+
+// Returns the type `Dim<...>` representing the dimension given as formula of
+// the base dimensions.
+fn codegen_dim_type(dim: &DerivedAs) -> TokenStream {
+    let exps = BASE_DIMS.iter().map(|sym| {
+        let exp = dim
+            .factors
+            .iter()
+            .filter(|(ident, _)| ident == sym)
+            .map(|(_, exp)| *exp)
+            .sum::<i32>();
+        codegen_int_type(exp)
+    });
+    quote!(qty_dimension::Dim<#(#exps),*>)
+}
+
+// Returns the type of the dimension resulting from the given formula of
+// quantities.
+fn codegen_formula_dim_type(derived_as: &DerivedAs) -> TokenStream {
+    let mut code = quote!(qty_dimension::One);
+    for (ident, exp) in &derived_as.factors {
+        let factor_dim = quote!(
+            <#ident as qty_dimension::HasDimension>::Dim
+        );
+        for _ in 0..exp.abs() {
+            code = if *exp > 0 {
+                quote!(<#code as qty_dimension::DimMul<#factor_dim>>::Output)
+            } else {
+                quote!(<#code as qty_dimension::DimDiv<#factor_dim>>::Output)
+            };
+        }
+    }
+    code
+}
+
+pub(crate) fn codegen_impl_dimension(
+    qty_ident: &syn::Ident,
+    unit_enum_ident: &syn::Ident,
+    single_unit_ident: Option<&syn::Ident>,
+    derive: &Derive,
+    dim: &DerivedAs,
+) -> TokenStream {
+    let dim_type = codegen_dim_type(dim);
+    let system = match &derive.system {
+        Some(path) => quote!(#path),
+        None => quote!(qty_dimension::SI),
+    };
+    let (code_fn_value_and_scale, code_fn_from_value_and_scale) =
+        match single_unit_ident {
+            Some(unit_ident) => (
+                quote!(
+                    (self.value(), <Amount as QuantityValue>::ONE)
+                ),
+                quote!(
+                    #qty_ident::new(
+                        value.mul_amount(scale),
+                        #unit_enum_ident::#unit_ident
+                    )
+                ),
+            ),
+            None => (
+                quote!(qty_dimension::value_and_scale(self)),
+                quote!(qty_dimension::from_value_and_scale(value, scale)),
+            ),
+        };
+    check_formulas(qty_ident, derive);
+    let mut code_checks = TokenStream::new();
+    for derived_as in &derive.derives {
+        let formula_dim_type = codegen_formula_dim_type(derived_as);
+        code_checks = quote!(
+            #code_checks
+            const _: () = qty_dimension::assert_same_dimension::<
+                #formula_dim_type,
+                <#qty_ident as qty_dimension::HasDimension>::Dim,
+            >();
+        );
+    }
+    quote!(
+        impl<V: QuantityValue> qty_dimension::HasDimension for #qty_ident<V> {
+            type Dim = #dim_type;
+            type Value = V;
+            type System = #system;
+            #[inline(always)]
+            fn value_and_scale(&self) -> (V, Amount) {
+                #code_fn_value_and_scale
+            }
+        }
+        impl qty_dimension::QuantityOfDim<#dim_type> for #system {
+            type Qty<V: QuantityValue> = #qty_ident<V>;
+            #[inline(always)]
+            fn from_value_and_scale<V: QuantityValue>(
+                value: V,
+                scale: Amount,
+            ) -> #qty_ident<V> {
+                #code_fn_from_value_and_scale
+            }
+        }
+        impl<V: QuantityValue, R> Mul<R> for #qty_ident<V>
+        where
+            Self: qty_dimension::QtyMul<R>,
+        {
+            type Output = <Self as qty_dimension::QtyMul<R>>::Output;
+            #[inline(always)]
+            fn mul(self, rhs: R) -> Self::Output {
+                qty_dimension::QtyMul::qty_mul(self, rhs)
+            }
+        }
+        impl<'a, V: QuantityValue, R> Mul<R> for &'a #qty_ident<V>
+        where
+            Self: qty_dimension::QtyMul<R>,
+        {
+            type Output = <Self as qty_dimension::QtyMul<R>>::Output;
+            #[inline(always)]
+            fn mul(self, rhs: R) -> Self::Output {
+                qty_dimension::QtyMul::qty_mul(self, rhs)
+            }
+        }
+        impl<V: QuantityValue, R> Div<R> for #qty_ident<V>
+        where
+            Self: qty_dimension::QtyDiv<R>,
+        {
+            type Output = <Self as qty_dimension::QtyDiv<R>>::Output;
+            #[inline(always)]
+            fn div(self, rhs: R) -> Self::Output {
+                qty_dimension::QtyDiv::qty_div(self, rhs)
+            }
+        }
+        impl<'a, V: QuantityValue, R> Div<R> for &'a #qty_ident<V>
+        where
+            Self: qty_dimension::QtyDiv<R>,
+        {
+            type Output = <Self as qty_dimension::QtyDiv<R>>::Output;
+            #[inline(always)]
+            fn div(self, rhs: R) -> Self::Output {
+                qty_dimension::QtyDiv::qty_div(self, rhs)
+            }
+        }
+        impl<V: QuantityValue> TryFrom<AnyQuantity<V>> for #qty_ident<V> {
+            type Error = DimensionError;
+            #[inline(always)]
+            fn try_from(qty: AnyQuantity<V>) -> Result<Self, Self::Error> {
+                qty.downcast()
+            }
+        }
+        #code_checks
+    )
+}
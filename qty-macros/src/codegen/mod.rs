@@ -1,12 +1,15 @@
 pub mod unit;
 pub mod quantity;
+pub mod dimension;
 
 use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort_call_site;
 use quote::quote;
 
 use crate::parsing::*;
 use crate::codegen::unit::*;
 use crate::codegen::quantity::*;
+use crate::codegen::dimension::*;
 
 pub(crate) fn codegen(
     qty_def: &QtyDef,
@@ -16,6 +19,25 @@ pub(crate) fn codegen(
     let unit_enum_ident =
         syn::Ident::new(&format!("{}Unit", qty_ident), Span::call_site());
     let code_attrs = codegen_attrs(attrs);
+    let dim = qty_def.derived_by.as_ref().and_then(|d| d.dim.as_ref());
+    let with_dim = dim.is_some();
+    let interval =
+        qty_def.derived_by.as_ref().and_then(|d| d.interval.as_ref());
+    if with_dim && qty_def.units.iter().any(|unit| unit.offset.is_some()) {
+        abort_call_site!(
+            "Quantity `{}` with offset units can't have a dimension.",
+            qty_ident
+        )
+    }
+    if interval.is_some()
+        && (qty_def.ref_unit_ident.is_none() || qty_def.units.len() == 1)
+    {
+        abort_call_site!(
+            "Quantity `{}` must have a reference unit and at least one \
+             other unit in order to have an interval.",
+            qty_ident
+        )
+    }
     let code_qty = if qty_def.units.len() == 1 {
         let unit_ident = qty_def.units[0].unit_ident.clone();
         let unit_name = qty_def.units[0].name.clone();
@@ -26,8 +48,16 @@ pub(crate) fn codegen(
             &unit_ident,
             &unit_name,
             &unit_symbol,
+            with_dim,
         )
     } else if qty_def.ref_unit_ident.is_none() {
+        if with_dim {
+            abort_call_site!(
+                "Quantity `{}` without reference unit can't have a \
+                 dimension.",
+                qty_ident
+            )
+        }
         codegen_qty_without_ref_unit(
             &qty_ident,
             &unit_enum_ident,
@@ -36,11 +66,32 @@ pub(crate) fn codegen(
     } else {
         let ref_unit_ident: &syn::Ident =
             qty_def.ref_unit_ident.as_ref().unwrap();
-        codegen_qty_with_ref_unit(
+        let code_ops = match interval {
+            Some(interval_ident) => {
+                codegen_impl_point_ops(&qty_ident, interval_ident)
+            }
+            None => codegen_impl_ref_unit_ops(&qty_ident, with_dim),
+        };
+        let code_qty = codegen_qty_with_ref_unit(
             &qty_ident,
             &unit_enum_ident,
             ref_unit_ident,
             &qty_def.units,
+            interval.is_some(),
+        );
+        quote!(
+            #code_qty
+            #code_ops
+        )
+    };
+    // points can't be negated, added up or scaled
+    let code_impl_arith_ops = if interval.is_some() {
+        TokenStream::new()
+    } else {
+        codegen_impl_arith_ops(
+            &qty_ident,
+            &unit_enum_ident,
+            qty_def.ref_unit_ident.is_some() && qty_def.units.len() > 1,
         )
     };
     let code_unit_consts =
@@ -48,32 +99,43 @@ pub(crate) fn codegen(
     let code_impl_mul =
         codegen_impl_mul_amnt_unit(&qty_ident, &unit_enum_ident);
     let code_impl_unit_display = codegen_impl_unit_display(&unit_enum_ident);
-    let code_impl_std_traits = codegen_impl_std_traits(&qty_ident);
-    let code_mul_div_base_qties = {
-        let mut code = quote::quote!();
-
-        match &qty_def.derived_by {
-            Some(derive) => {
-                for derived_as in &derive.derives {
-                    let d_a = codegen_impl_mul_div_qties(&qty_ident, Some(derived_as));
-                    code = quote::quote!(
-                        #code
-                        #d_a
-                    )
-                }
-            },
-            None => todo!("codegen(): None branch not implemented yet")
+    // with a dimension, the multiplication and division by values and rates
+    // is covered by the dimensional analysis, and points can't be scaled
+    let code_impl_std_traits =
+        codegen_impl_std_traits(&qty_ident, !with_dim && interval.is_none());
+    let code_impl_serde = codegen_impl_serde(&qty_ident, &unit_enum_ident);
+    let code_mul_div_base_qties = match (&qty_def.derived_by, dim) {
+        (Some(derive), Some(dim)) => {
+            let single_unit_ident = if qty_def.units.len() == 1 {
+                Some(&qty_def.units[0].unit_ident)
+            } else {
+                None
+            };
+            codegen_impl_dimension(
+                &qty_ident,
+                &unit_enum_ident,
+                single_unit_ident,
+                derive,
+                dim,
+            )
         }
-
-        code
+        (Some(derive), None) => codegen_impl_mul_div_qties(&qty_ident, derive),
+        _ => TokenStream::new(),
+    };
+    let code_impl_powers = match &qty_def.derived_by {
+        Some(derive) => codegen_impl_powers(&qty_ident, derive),
+        None => TokenStream::new(),
     };
     quote!(
         #code_attrs
         #code_qty
+        #code_impl_arith_ops
         #code_unit_consts
         #code_impl_mul
         #code_impl_unit_display
         #code_impl_std_traits
+        #code_impl_serde
         #code_mul_div_base_qties
+        #code_impl_powers
     )
 }
@@ -8,29 +8,54 @@ use crate::codegen::unit::*;
 pub fn codegen_impl_quantity(
     qty_ident: &syn::Ident,
     unit_enum_ident: &syn::Ident,
+    code_fns: TokenStream,
+    code_additive_fns: Option<TokenStream>,
 ) -> TokenStream {
+    // points don't implement `AdditiveQuantity`
+    let code_impl_additive = match code_additive_fns {
+        Some(code_fns) => quote!(
+            impl<V: QuantityValue> AdditiveQuantity for #qty_ident<V> {
+                #code_fns
+            }
+        ),
+        None => TokenStream::new(),
+    };
+    let new_doc = format!(
+        "Returns a new instance of `{}` (usable in const contexts).",
+        qty_ident
+    );
     quote!(
         #[derive(Copy, Clone, Debug)]
-        pub struct #qty_ident {
-            value: Amount,
+        pub struct #qty_ident<V: QuantityValue = Amount> {
+            value: V,
             unit: #unit_enum_ident
         }
-        impl const Quantity for #qty_ident {
+        impl<V: QuantityValue> #qty_ident<V> {
+            #[doc = #new_doc]
+            #[inline(always)]
+            pub const fn new(value: V, unit: #unit_enum_ident) -> Self {
+                Self { value, unit }
+            }
+        }
+        impl<V: QuantityValue> Quantity for #qty_ident<V> {
+            type Value = V;
             type UnitType = #unit_enum_ident;
             #[inline(always)]
-            fn new(value: Amount, unit: Self::UnitType) -> Self {
+            fn new(value: V, unit: Self::UnitType) -> Self {
                 Self { value, unit }
             }
             #[inline(always)]
-            fn value(&self) -> Amount {
+            fn value(&self) -> V {
                 self.value
             }
             #[inline(always)]
             fn unit(&self) -> Self::UnitType {
                 self.unit
             }
+            #code_fns
         }
-        impl QuantityImpl for #qty_ident {}
+        #code_impl_additive
+        impl<V: QuantityValue> QuantityImpl for #qty_ident<V> {}
     )
 }
 
@@ -39,6 +64,7 @@ pub(crate) fn codegen_qty_with_ref_unit(
     unit_enum_ident: &syn::Ident,
     ref_unit_ident: &syn::Ident,
     units: &Vec<UnitDef>,
+    is_point: bool,
 ) -> TokenStream {
     let code_unit_variants = codegen_unit_variants(units);
     let code_unit_variants_array =
@@ -46,9 +72,50 @@ pub(crate) fn codegen_qty_with_ref_unit(
     let code_fn_name = codegen_fn_name(units);
     let code_fn_symbol = codegen_fn_symbol(units);
     let code_fn_si_prefix = codegen_fn_si_prefix(units);
+    let code_fn_binary_prefix = codegen_fn_binary_prefix(units);
     let code_fn_scale = codegen_fn_scale(units);
+    let code_fn_offset = codegen_fn_offset(units);
     let unit_doc = format!("Unit of quantity `{}`.", qty_ident);
-    let code_impl_quantity = codegen_impl_quantity(qty_ident, unit_enum_ident);
+    // the generic methods of `Quantity` and `AdditiveQuantity` convert `rhs`
+    // to the unit of `self`
+    let code_fns = quote!(
+        #[inline(always)]
+        fn eq(&self, other: &Self) -> bool {
+            <Self as HasRefUnit>::eq(self, other)
+        }
+        #[inline(always)]
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            <Self as HasRefUnit>::partial_cmp(self, other)
+        }
+        #[inline(always)]
+        fn div_qty(self, rhs: Self) -> V {
+            <Self as HasRefUnit>::div(self, rhs)
+        }
+    );
+    let code_additive_fns = if is_point {
+        None
+    } else {
+        Some(quote!(
+            #[inline(always)]
+            fn add_qty(self, rhs: Self) -> Self {
+                <Self as HasRefUnit>::add(self, rhs)
+            }
+            #[inline(always)]
+            fn sub_qty(self, rhs: Self) -> Self {
+                <Self as HasRefUnit>::sub(self, rhs)
+            }
+            #[inline(always)]
+            fn rem_qty(self, rhs: Self) -> Self {
+                <Self as HasRefUnit>::rem(self, rhs)
+            }
+        ))
+    };
+    let code_impl_quantity = codegen_impl_quantity(
+        qty_ident,
+        unit_enum_ident,
+        code_fns,
+        code_additive_fns,
+    );
     quote!(
         #code_impl_quantity
         #[doc = #unit_doc]
@@ -65,47 +132,185 @@ pub(crate) fn codegen_qty_with_ref_unit(
             #code_fn_name
             #code_fn_symbol
             #code_fn_si_prefix
+            #code_fn_binary_prefix
         }
         impl LinearScaledUnit for #unit_enum_ident {
             const REF_UNIT: Self = Self::#ref_unit_ident;
             #code_fn_scale
+            #code_fn_offset
         }
-        impl HasRefUnit for #qty_ident {
+        impl<V: QuantityValue> HasRefUnit for #qty_ident<V> {
             const REF_UNIT: #unit_enum_ident =
                 #unit_enum_ident::#ref_unit_ident;
         }
-        impl Eq for #qty_ident {}
-        impl PartialEq<Self> for #qty_ident {
+        impl<V: QuantityValue> Eq for #qty_ident<V> {}
+        impl<V: QuantityValue> PartialEq<Self> for #qty_ident<V> {
             #[inline(always)]
             fn eq(&self, other: &Self) -> bool {
                 <Self as HasRefUnit>::eq(self, other)
             }
         }
-        impl PartialOrd for #qty_ident {
+        impl<V: QuantityValue> PartialOrd for #qty_ident<V> {
             #[inline(always)]
             fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
                 <Self as HasRefUnit>::partial_cmp(self, other)
             }
         }
-        impl Add<Self> for #qty_ident {
+    )
+}
+
+pub(crate) fn codegen_impl_ref_unit_ops(
+    qty_ident: &syn::Ident,
+    with_dim: bool,
+) -> TokenStream {
+    // with a dimension, `Div<Self>` is covered by the dimensional analysis
+    let code_impl_div_self = if with_dim {
+        TokenStream::new()
+    } else {
+        quote!(
+            impl<V: QuantityValue> Div<Self> for #qty_ident<V> {
+                type Output = V;
+                #[inline(always)]
+                fn div(self, rhs: Self) -> Self::Output {
+                    <Self as HasRefUnit>::div(self, rhs)
+                }
+            }
+        )
+    };
+    quote!(
+        impl<V: QuantityValue> Add<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn add(self, rhs: Self) -> Self::Output {
                 <Self as HasRefUnit>::add(self, rhs)
             }
         }
-        impl Sub<Self> for #qty_ident {
+        impl<V: QuantityValue> Sub<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn sub(self, rhs: Self) -> Self::Output {
                 <Self as HasRefUnit>::sub(self, rhs)
             }
         }
-        impl Div<Self> for #qty_ident {
-            type Output = Amount;
+        #code_impl_div_self
+    )
+}
+
+pub(crate) fn codegen_impl_arith_ops(
+    qty_ident: &syn::Ident,
+    unit_enum_ident: &syn::Ident,
+    with_ref_unit: bool,
+) -> TokenStream {
+    // the sum of an empty iterator is zero in the reference unit, if there
+    // is one, otherwise in the first unit
+    let (code_rem, code_default_unit) = if with_ref_unit {
+        (
+            quote!(<Self as HasRefUnit>::rem(self, rhs)),
+            quote!(<Self as HasRefUnit>::REF_UNIT),
+        )
+    } else {
+        (
+            quote!(<Self as AdditiveQuantity>::rem_qty(self, rhs)),
+            quote!(#unit_enum_ident::VARIANTS[0]),
+        )
+    };
+    quote!(
+        impl<V: QuantityValue> Neg for #qty_ident<V> {
+            type Output = Self;
+            #[inline(always)]
+            fn neg(self) -> Self::Output {
+                Self::new(-self.value(), self.unit())
+            }
+        }
+        impl<V: QuantityValue> Rem<Self> for #qty_ident<V> {
+            type Output = Self;
             #[inline(always)]
-            fn div(self, rhs: Self) -> Self::Output {
-                <Self as HasRefUnit>::div(self, rhs)
+            fn rem(self, rhs: Self) -> Self::Output {
+                #code_rem
+            }
+        }
+        impl<V: QuantityValue> AddAssign<Self> for #qty_ident<V> {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+        impl<V: QuantityValue> SubAssign<Self> for #qty_ident<V> {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+        impl<V: QuantityValue> MulAssign<V> for #qty_ident<V>
+        where
+            Self: Mul<V, Output = Self>,
+        {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: V) {
+                *self = *self * rhs;
+            }
+        }
+        impl<V: QuantityValue> DivAssign<V> for #qty_ident<V>
+        where
+            Self: Div<V, Output = Self>,
+        {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: V) {
+                *self = *self / rhs;
+            }
+        }
+        impl<V: QuantityValue> Sum<Self> for #qty_ident<V> {
+            fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+                // the sum takes the unit of the first item
+                match iter.next() {
+                    Some(first) => iter.fold(first, |acc, qty| acc + qty),
+                    None => Self::new(V::ZERO, #code_default_unit),
+                }
+            }
+        }
+        impl<'a, V: QuantityValue> Sum<&'a Self> for #qty_ident<V> {
+            #[inline(always)]
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+    )
+}
+
+pub(crate) fn codegen_impl_point_ops(
+    qty_ident: &syn::Ident,
+    interval_ident: &syn::Ident,
+) -> TokenStream {
+    quote!(
+        impl<V: QuantityValue> PointQuantity for #qty_ident<V> {
+            type Interval = #interval_ident<V>;
+        }
+        impl<V: QuantityValue> Sub<Self> for #qty_ident<V> {
+            type Output = #interval_ident<V>;
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self::Output {
+                <Self as PointQuantity>::diff(self, rhs)
+            }
+        }
+        impl<V: QuantityValue> Add<#interval_ident<V>> for #qty_ident<V> {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: #interval_ident<V>) -> Self::Output {
+                <Self as PointQuantity>::add_interval(self, rhs)
+            }
+        }
+        impl<V: QuantityValue> Sub<#interval_ident<V>> for #qty_ident<V> {
+            type Output = Self;
+            #[inline(always)]
+            fn sub(self, rhs: #interval_ident<V>) -> Self::Output {
+                <Self as PointQuantity>::sub_interval(self, rhs)
+            }
+        }
+        impl<V: QuantityValue> Add<#qty_ident<V>> for #interval_ident<V> {
+            type Output = #qty_ident<V>;
+            #[inline(always)]
+            fn add(self, rhs: #qty_ident<V>) -> Self::Output {
+                <#qty_ident<V> as PointQuantity>::add_interval(rhs, self)
             }
         }
     )
@@ -122,7 +327,12 @@ pub(crate) fn codegen_qty_without_ref_unit(
     let code_fn_name = codegen_fn_name(units);
     let code_fn_symbol = codegen_fn_symbol(units);
     let unit_doc = format!("Unit of quantity `{}`.", qty_ident);
-    let code_impl_quantity = codegen_impl_quantity(qty_ident, unit_enum_ident);
+    let code_impl_quantity = codegen_impl_quantity(
+        qty_ident,
+        unit_enum_ident,
+        TokenStream::new(),
+        Some(TokenStream::new()),
+    );
     quote!(
         #code_impl_quantity
         #[doc = #unit_doc]
@@ -138,93 +348,137 @@ pub(crate) fn codegen_qty_without_ref_unit(
             #code_fn_symbol
             fn si_prefix(&self) -> Option<SIPrefix> { None }
         }
-        impl Eq for #qty_ident {}
-        impl PartialEq<Self> for #qty_ident {
+        impl<V: QuantityValue> Eq for #qty_ident<V> {}
+        impl<V: QuantityValue> PartialEq<Self> for #qty_ident<V> {
             #[inline(always)]
             fn eq(&self, other: &Self) -> bool {
-                <Self as QuantityImpl>::eq(self, other)
+                <Self as Quantity>::eq(self, other)
             }
         }
-        impl PartialOrd for #qty_ident {
+        impl<V: QuantityValue> PartialOrd for #qty_ident<V> {
             #[inline(always)]
             fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-                <Self as QuantityImpl>::partial_cmp(self, other)
+                <Self as Quantity>::partial_cmp(self, other)
             }
         }
-        impl Add<Self> for #qty_ident {
+        impl<V: QuantityValue> Add<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn add(self, rhs: Self) -> Self::Output {
-                <Self as QuantityImpl>::add(self, rhs)
+                <Self as AdditiveQuantity>::add_qty(self, rhs)
             }
         }
-        impl Sub<Self> for #qty_ident {
+        impl<V: QuantityValue> Sub<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn sub(self, rhs: Self) -> Self::Output {
-                <Self as QuantityImpl>::sub(self, rhs)
+                <Self as AdditiveQuantity>::sub_qty(self, rhs)
             }
         }
-        impl Div<Self> for #qty_ident {
-            type Output = Amount;
+        impl<V: QuantityValue> Div<Self> for #qty_ident<V> {
+            type Output = V;
             #[inline(always)]
             fn div(self, rhs: Self) -> Self::Output {
-                <Self as QuantityImpl>::div(self, rhs)
+                <Self as Quantity>::div_qty(self, rhs)
             }
         }
     )
 }
 
-pub fn codegen_impl_std_traits(qty_ident: &syn::Ident) -> TokenStream {
+pub fn codegen_impl_std_traits(
+    qty_ident: &syn::Ident,
+    with_scalar_ops: bool,
+) -> TokenStream {
+    let code_impl_ops = if !with_scalar_ops {
+        TokenStream::new()
+    } else {
+        quote!(
+            impl<V: QuantityValue> Mul<V> for #qty_ident<V> {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(self, rhs: V) -> Self::Output {
+                    Self::Output::new(self.value() * rhs, self.unit())
+                }
+            }
+            impl<V: QuantityValue> Div<V> for #qty_ident<V> {
+                type Output = Self;
+                #[inline(always)]
+                fn div(self, rhs: V) -> Self::Output {
+                    Self::Output::new(self.value() / rhs, self.unit())
+                }
+            }
+            impl<V, TQ> Mul<Rate<TQ, Self>> for #qty_ident<V>
+            where
+                V: QuantityValue,
+                TQ: Quantity<Value = V>,
+            {
+                type Output = TQ;
+
+                fn mul(self, rhs: Rate<TQ, Self>) -> Self::Output {
+                    let amnt: V = (self / Self::new(V::ONE, rhs.per_unit()))
+                        / rhs.per_unit_multiple();
+                    Self::Output::new(
+                        amnt * rhs.term_amount(),
+                        rhs.term_unit()
+                    )
+                }
+            }
+            impl<V, PQ> Div<Rate<Self, PQ>> for #qty_ident<V>
+            where
+                V: QuantityValue,
+                PQ: Quantity<Value = V>,
+            {
+                type Output = PQ;
+
+                fn div(self, rhs: Rate<Self, PQ>) -> Self::Output {
+                    let amnt: V = (self / Self::new(V::ONE, rhs.term_unit()))
+                        / rhs.term_amount();
+                    Self::Output::new(
+                        amnt * rhs.per_unit_multiple(),
+                        rhs.per_unit()
+                    )
+                }
+            }
+        )
+    };
     quote!(
-        impl fmt::Display for #qty_ident {
+        impl<V: QuantityValue> fmt::Display for #qty_ident<V> {
             #[inline(always)]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                <Self as QuantityImpl>::fmt(self, f)
+                <Self as Quantity>::fmt(self, f)
             }
         }
-        impl Mul<#qty_ident> for Amount {
-            type Output = #qty_ident;
-            #[inline(always)]
-            fn mul(self, rhs: #qty_ident) -> Self::Output {
-                Self::Output::new(self * rhs.value(), rhs.unit())
-            }
-        }
-        impl Mul<Amount> for #qty_ident {
-            type Output = Self;
+        impl<V> core::str::FromStr for #qty_ident<V>
+        where
+            V: QuantityValue + core::str::FromStr,
+        {
+            type Err = ParseQuantityError;
             #[inline(always)]
-            fn mul(self, rhs: Amount) -> Self::Output {
-                Self::Output::new(self.value() * rhs, self.unit())
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <Self as QuantityImpl>::parse(s)
             }
         }
-        impl Div<Amount> for #qty_ident {
-            type Output = Self;
+        impl<V> core::convert::TryFrom<&str> for #qty_ident<V>
+        where
+            V: QuantityValue + core::str::FromStr,
+        {
+            type Error = ParseQuantityError;
             #[inline(always)]
-            fn div(self, rhs: Amount) -> Self::Output {
-                Self::Output::new(self.value() / rhs, self.unit())
+            fn try_from(s: &str) -> Result<Self, Self::Error> {
+                <Self as QuantityImpl>::parse(s)
             }
         }
-        impl<TQ: Quantity> Mul<Rate<TQ, Self>> for #qty_ident {
-            type Output = TQ;
-
-            fn mul(self, rhs: Rate<TQ, Self>) -> Self::Output {
-                let amnt: Amount =
-                    (self / rhs.per_unit().as_qty()) / rhs.per_unit_multiple();
-                Self::Output::new(amnt * rhs.term_amount(), rhs.term_unit())
-            }
-        }
-        impl<PQ: Quantity> Div<Rate<Self, PQ>> for #qty_ident {
-            type Output = PQ;
+        #code_impl_ops
+    )
+}
 
-            fn div(self, rhs: Rate<Self, PQ>) -> Self::Output {
-                let amnt: Amount =
-                    (self / rhs.term_unit().as_qty()) / rhs.term_amount();
-                Self::Output::new(
-                    amnt * rhs.per_unit_multiple(),
-                    rhs.per_unit()
-                )
-            }
-        }
+pub fn codegen_impl_serde(
+    qty_ident: &syn::Ident,
+    unit_enum_ident: &syn::Ident,
+) -> TokenStream {
+    // the macro expands to nothing unless feature `serde` is enabled
+    quote!(
+        impl_serde!(#qty_ident, #unit_enum_ident);
     )
 }
 
@@ -233,11 +487,8 @@ pub fn codegen_impl_qty_sqared(
     qty_ident: &syn::Ident,
 ) -> TokenStream {
     quote!(
-        impl Mul<Self> for #qty_ident
-        where
-            Self: HasRefUnit,
-        {
-            type Output = #res_qty_ident;
+        impl<V: QuantityValue> Mul<Self> for #qty_ident<V> {
+            type Output = #res_qty_ident<V>;
             fn mul(self, rhs: Self) -> Self::Output {
                 let scale =
                     self.unit().scale() * rhs.unit().scale();
@@ -246,22 +497,22 @@ pub fn codegen_impl_qty_sqared(
                         Self::Output::new(self.value() * rhs.value(), unit),
                     None =>
                         <Self::Output as HasRefUnit>::_fit(
-                            self.value() * rhs.value() * scale
+                            (self.value() * rhs.value()).mul_amount(scale)
                         )
                 }
             }
         }
-        impl<'a> Mul<#qty_ident> for &'a #qty_ident
+        impl<'a, V: QuantityValue> Mul<#qty_ident<V>> for &'a #qty_ident<V>
         where
-            #qty_ident: Mul<#qty_ident>,
+            #qty_ident<V>: Mul<#qty_ident<V>>,
         {
-            type Output = <#qty_ident as Mul<#qty_ident>>::Output;
+            type Output = <#qty_ident<V> as Mul<#qty_ident<V>>>::Output;
             #[inline(always)]
-            fn mul(self, rhs: #qty_ident) -> Self::Output {
+            fn mul(self, rhs: #qty_ident<V>) -> Self::Output {
                 Mul::mul(*self, rhs)
             }
         }
-        impl Mul<&Self> for #qty_ident
+        impl<V: QuantityValue> Mul<&Self> for #qty_ident<V>
         where
             Self: Mul<Self>,
         {
@@ -271,11 +522,11 @@ pub fn codegen_impl_qty_sqared(
                 Mul::mul(self, *rhs)
             }
         }
-        impl Mul<Self> for &#qty_ident
+        impl<V: QuantityValue> Mul<Self> for &#qty_ident<V>
         where
-            #qty_ident: Mul<#qty_ident>,
+            #qty_ident<V>: Mul<#qty_ident<V>>,
         {
-            type Output = <#qty_ident as Mul<#qty_ident>>::Output;
+            type Output = <#qty_ident<V> as Mul<#qty_ident<V>>>::Output;
             #[inline(always)]
             fn mul(self, rhs: Self) -> Self::Output {
                 Mul::mul(*self, *rhs)
@@ -290,13 +541,9 @@ pub fn codegen_impl_qty_mul_qty(
     rhs_qty_ident: &syn::Ident,
 ) -> TokenStream {
     quote!(
-        impl Mul<#rhs_qty_ident> for #lhs_qty_ident
-        where
-            Self: HasRefUnit,
-            #rhs_qty_ident: HasRefUnit,
-        {
-            type Output = #res_qty_ident;
-            fn mul(self, rhs: #rhs_qty_ident) -> Self::Output {
+        impl<V: QuantityValue> Mul<#rhs_qty_ident<V>> for #lhs_qty_ident<V> {
+            type Output = #res_qty_ident<V>;
+            fn mul(self, rhs: #rhs_qty_ident<V>) -> Self::Output {
                 let scale =
                     self.unit().scale() * rhs.unit().scale();
                 match Self::Output::unit_from_scale(scale) {
@@ -304,85 +551,56 @@ pub fn codegen_impl_qty_mul_qty(
                         Self::Output::new(self.value() * rhs.value(), unit),
                     None =>
                         <Self::Output as HasRefUnit>::_fit(
-                            self.value() * rhs.value() * scale
+                            (self.value() * rhs.value()).mul_amount(scale)
                         )
                 }
             }
         }
-        impl<'a> Mul<#rhs_qty_ident> for &'a #lhs_qty_ident
+        impl<'a, V: QuantityValue> Mul<#rhs_qty_ident<V>>
+            for &'a #lhs_qty_ident<V>
         where
-            #lhs_qty_ident: Mul<#rhs_qty_ident>,
+            #lhs_qty_ident<V>: Mul<#rhs_qty_ident<V>>,
         {
-            type Output = <#lhs_qty_ident as Mul<#rhs_qty_ident>>::Output;
+            type Output =
+                <#lhs_qty_ident<V> as Mul<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn mul(self, rhs: #rhs_qty_ident) -> Self::Output {
+            fn mul(self, rhs: #rhs_qty_ident<V>) -> Self::Output {
                 Mul::mul(*self, rhs)
             }
         }
-        impl Mul<&#rhs_qty_ident> for #lhs_qty_ident
+        impl<V: QuantityValue> Mul<&#rhs_qty_ident<V>> for #lhs_qty_ident<V>
         where
-            Self: Mul<#rhs_qty_ident>,
+            Self: Mul<#rhs_qty_ident<V>>,
         {
-            type Output = <Self as Mul<#rhs_qty_ident>>::Output;
+            type Output = <Self as Mul<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn mul(self, rhs: &#rhs_qty_ident) -> Self::Output {
+            fn mul(self, rhs: &#rhs_qty_ident<V>) -> Self::Output {
                 Mul::mul(self, *rhs)
             }
         }
-        impl Mul<&#rhs_qty_ident> for &#lhs_qty_ident
+        impl<V: QuantityValue> Mul<&#rhs_qty_ident<V>> for &#lhs_qty_ident<V>
         where
-            #lhs_qty_ident: Mul<#rhs_qty_ident>,
+            #lhs_qty_ident<V>: Mul<#rhs_qty_ident<V>>,
         {
-            type Output = <#lhs_qty_ident as Mul<#rhs_qty_ident>>::Output;
+            type Output =
+                <#lhs_qty_ident<V> as Mul<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn mul(self, rhs: &#rhs_qty_ident) -> Self::Output {
+            fn mul(self, rhs: &#rhs_qty_ident<V>) -> Self::Output {
                 Mul::mul(*self, *rhs)
             }
         }
     )
 }
 
-pub fn codegen_impl_mul_qties(
-    res_qty_ident: &syn::Ident,
-    lhs_qty_ident: &syn::Ident,
-    rhs_qty_ident: &syn::Ident,
-) -> TokenStream {
-    if lhs_qty_ident == rhs_qty_ident {
-        let code = codegen_impl_qty_sqared(res_qty_ident, lhs_qty_ident);
-        quote!(
-            #code
-        )
-    } else {
-        let code_lr = codegen_impl_qty_mul_qty(
-            res_qty_ident,
-            lhs_qty_ident,
-            rhs_qty_ident,
-        );
-        let code_rl = codegen_impl_qty_mul_qty(
-            res_qty_ident,
-            rhs_qty_ident,
-            lhs_qty_ident,
-        );
-        quote!(
-            #code_lr
-            #code_rl
-        )
-    }
-}
-
 pub fn codegen_impl_div_qties(
     res_qty_ident: &syn::Ident,
     lhs_qty_ident: &syn::Ident,
     rhs_qty_ident: &syn::Ident,
 ) -> TokenStream {
     quote!(
-        impl Div<#rhs_qty_ident> for #lhs_qty_ident
-        where
-            Self: HasRefUnit,
-            #rhs_qty_ident: HasRefUnit,
-        {
-            type Output = #res_qty_ident;
-            fn div(self, rhs: #rhs_qty_ident) -> Self::Output {
+        impl<V: QuantityValue> Div<#rhs_qty_ident<V>> for #lhs_qty_ident<V> {
+            type Output = #res_qty_ident<V>;
+            fn div(self, rhs: #rhs_qty_ident<V>) -> Self::Output {
                 let scale =
                     self.unit().scale() / rhs.unit().scale();
                 match Self::Output::unit_from_scale(scale) {
@@ -390,108 +608,229 @@ pub fn codegen_impl_div_qties(
                         Self::Output::new(self.value() / rhs.value(), unit),
                     None =>
                         <Self::Output as HasRefUnit>::_fit(
-                            (self.value() / rhs.value()) * scale
+                            (self.value() / rhs.value()).mul_amount(scale)
                         )
                 }
             }
         }
-        impl<'a> Div<#rhs_qty_ident> for &'a #lhs_qty_ident
+        impl<'a, V: QuantityValue> Div<#rhs_qty_ident<V>>
+            for &'a #lhs_qty_ident<V>
         where
-            #lhs_qty_ident: Div<#rhs_qty_ident>,
+            #lhs_qty_ident<V>: Div<#rhs_qty_ident<V>>,
         {
-            type Output = <#lhs_qty_ident as Div<#rhs_qty_ident>>::Output;
+            type Output =
+                <#lhs_qty_ident<V> as Div<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn div(self, rhs: #rhs_qty_ident) -> Self::Output {
+            fn div(self, rhs: #rhs_qty_ident<V>) -> Self::Output {
                 Div::div(*self, rhs)
             }
         }
-        impl Div<&#rhs_qty_ident> for #lhs_qty_ident
+        impl<V: QuantityValue> Div<&#rhs_qty_ident<V>> for #lhs_qty_ident<V>
         where
-            Self: Div<#rhs_qty_ident>,
+            Self: Div<#rhs_qty_ident<V>>,
         {
-            type Output = <Self as Div<#rhs_qty_ident>>::Output;
+            type Output = <Self as Div<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn div(self, rhs: &#rhs_qty_ident) -> Self::Output {
+            fn div(self, rhs: &#rhs_qty_ident<V>) -> Self::Output {
                 Div::div(self, *rhs)
             }
         }
-        impl Div<&#rhs_qty_ident> for &#lhs_qty_ident
+        impl<V: QuantityValue> Div<&#rhs_qty_ident<V>> for &#lhs_qty_ident<V>
         where
-            #lhs_qty_ident: Div<#rhs_qty_ident>,
+            #lhs_qty_ident<V>: Div<#rhs_qty_ident<V>>,
         {
-            type Output = <#lhs_qty_ident as Div<#rhs_qty_ident>>::Output;
+            type Output =
+                <#lhs_qty_ident<V> as Div<#rhs_qty_ident<V>>>::Output;
             #[inline(always)]
-            fn div(self, rhs: &#rhs_qty_ident) -> Self::Output {
+            fn div(self, rhs: &#rhs_qty_ident<V>) -> Self::Output {
                 Div::div(*self, *rhs)
             }
         }
     )
 }
 
-pub(crate) fn codegen_impl_mul_div_qties(
+// Dimension of a quantity, given as exponents of the quantities used in a
+// formula.
+type Dim = Vec<i32>;
+
+// Returns all triples (lhs, op, rhs) -> res, where lhs, rhs and res are
+// quantities named in `derived_as` or the quantity `qty_ident` defined by it,
+// so that lhs op rhs has the same dimension as res.
+fn consistent_ops<'a>(
+    qty_ident: &'a syn::Ident,
+    derived_as: &'a DerivedAs,
+) -> Vec<(&'a syn::Ident, char, &'a syn::Ident, &'a syn::Ident)> {
+    let n_factors = derived_as.factors.len();
+    let mut qties: Vec<(&syn::Ident, Dim)> = Vec::with_capacity(n_factors + 1);
+    qties.push((
+        qty_ident,
+        derived_as.factors.iter().map(|(_, exp)| *exp).collect(),
+    ));
+    for (idx, (ident, _)) in derived_as.factors.iter().enumerate() {
+        let mut dim = vec![0; n_factors];
+        dim[idx] = 1;
+        qties.push((ident, dim));
+    }
+    let find_qty = |dim: &Dim| {
+        qties
+            .iter()
+            .find(|(_, qty_dim)| qty_dim == dim)
+            .map(|(ident, _)| *ident)
+    };
+    let mut ops = vec![];
+    for (lhs_ident, lhs_dim) in &qties {
+        for (rhs_ident, rhs_dim) in &qties {
+            let prod_dim: Dim =
+                lhs_dim.iter().zip(rhs_dim).map(|(l, r)| l + r).collect();
+            if let Some(res_ident) = find_qty(&prod_dim) {
+                ops.push((*lhs_ident, '*', *rhs_ident, res_ident));
+            }
+            let quot_dim: Dim =
+                lhs_dim.iter().zip(rhs_dim).map(|(l, r)| l - r).collect();
+            if let Some(res_ident) = find_qty(&quot_dim) {
+                ops.push((*lhs_ident, '/', *rhs_ident, res_ident));
+            }
+        }
+    }
+    ops
+}
+
+// Aborts if one of the formulas deriving `qty_ident` is not acceptable.
+pub(crate) fn check_formulas(qty_ident: &syn::Ident, derive: &Derive) {
+    for derived_as in &derive.derives {
+        if derived_as.factors.iter().any(|(ident, _)| ident == qty_ident) {
+            abort_call_site!(
+                "Quantity `{}` can't be derived from itself.", qty_ident
+            )
+        }
+        if derived_as.factors.len() < 2
+            && derived_as.factors.iter().all(|(_, exp)| *exp == 1)
+        {
+            abort_call_site!(
+                "Formula deriving quantity `{}` must contain an operator \
+                 or an exponent other than 1.",
+                qty_ident
+            )
+        }
+    }
+}
+
+// Returns `factor` * `factor` ... with `exp` factors.
+fn codegen_power(factor: &TokenStream, exp: i32) -> TokenStream {
+    let mut code = factor.clone();
+    for _ in 1..exp {
+        code = quote!(#code * #factor);
+    }
+    code
+}
+
+pub(crate) fn codegen_impl_powers(
     qty_ident: &syn::Ident,
-    derived_as: Option<&DerivedAs>,
+    derive: &Derive,
 ) -> TokenStream {
-    match derived_as {
-        None => TokenStream::new(),
-        Some(derived_as) => {
-            let lhs_qty_ident = &derived_as.lhs_ident;
-            let rhs_qty_ident = &derived_as.rhs_ident;
-            match derived_as.op {
-                syn::BinOp::Mul(_) => {
-                    let code_impl_mul = codegen_impl_mul_qties(
-                        qty_ident,
-                        lhs_qty_ident,
-                        rhs_qty_ident,
-                    );
-                    let code_impl_res_div_rhs = codegen_impl_div_qties(
-                        lhs_qty_ident,
-                        qty_ident,
-                        rhs_qty_ident,
-                    );
-                    let code_impl_res_div_lhs =
-                        if lhs_qty_ident == rhs_qty_ident {
-                            TokenStream::new()
-                        } else {
-                            codegen_impl_div_qties(
-                                rhs_qty_ident,
-                                qty_ident,
-                                lhs_qty_ident,
-                            )
-                        };
-                    quote!(
-                        #code_impl_mul
-                        #code_impl_res_div_rhs
-                        #code_impl_res_div_lhs
-                    )
-                }
-                syn::BinOp::Div(_) => {
-                    let code_impl_div = codegen_impl_div_qties(
-                        qty_ident,
-                        lhs_qty_ident,
-                        rhs_qty_ident,
-                    );
-                    let code_impl_mul_res = codegen_impl_mul_qties(
-                        lhs_qty_ident,
-                        qty_ident,
-                        rhs_qty_ident,
-                    );
-                    let code_impl_div_res = codegen_impl_div_qties(
-                        rhs_qty_ident,
-                        lhs_qty_ident,
-                        qty_ident,
-                    );
-                    quote!(
-                        #code_impl_div
-                        #code_impl_mul_res
-                        #code_impl_div_res
-                    )
+    // the formulas giving `qty_ident` as square or cube of a quantity
+    let mut powers: Vec<(&syn::Ident, i32)> = vec![];
+    for derived_as in &derive.derives {
+        if let [(ref base_qty_ident, exp @ (2 | 3))] = derived_as.factors[..] {
+            match powers.iter().find(|p| p.1 == exp) {
+                None => powers.push((base_qty_ident, exp)),
+                Some(known) if known.0 == base_qty_ident => {}
+                Some(known) => abort_call_site!(
+                    "Inconsistent formulas: `{}` is the power {} of `{}` \
+                     and `{}`.",
+                    qty_ident, exp, known.0, base_qty_ident
+                ),
+            }
+        }
+    }
+    let mut code = TokenStream::new();
+    for (base_qty_ident, exp) in powers {
+        let exp_lit = proc_macro2::Literal::i32_unsuffixed(exp);
+        let root_fn = if exp == 2 { quote!(sqrt) } else { quote!(cbrt) };
+        let code_value_pow = codegen_power(&quote!(self.value()), exp);
+        let code_scale_pow = codegen_power(&quote!(self.unit().scale()), exp);
+        let code_unit_scale_pow = codegen_power(&quote!(unit.scale()), exp);
+        code = quote!(
+            #code
+            impl<V: QuantityValue> QtyPow<#exp_lit> for #base_qty_ident<V> {
+                type Output = #qty_ident<V>;
+                fn pow(self) -> Self::Output {
+                    let scale = #code_scale_pow;
+                    let value = #code_value_pow;
+                    match Self::Output::unit_from_scale(scale) {
+                        Some(unit) => Self::Output::new(value, unit),
+                        None => <Self::Output as HasRefUnit>::_fit(
+                            value.mul_amount(scale)
+                        ),
+                    }
                 }
-                _ => {
-                    // should not happen!
-                    abort_call_site!("Internal error: wrong op detected!")
+            }
+            impl<V: QuantityValue> QtyRoot<#exp_lit> for #qty_ident<V> {
+                type Output = #base_qty_ident<V>;
+                fn root(self) -> Self::Output {
+                    let scale = self.unit().scale();
+                    let unit = <Self::Output as Quantity>::iter_units()
+                        .copied()
+                        .find(|unit| {
+                            unit.offset() == <Amount as QuantityValue>::ZERO
+                                && #code_unit_scale_pow == scale
+                        });
+                    match unit {
+                        Some(unit) => Self::Output::new(
+                            self.value().#root_fn(),
+                            unit
+                        ),
+                        None => <Self::Output as HasRefUnit>::_fit(
+                            self.equiv_amount(<Self as HasRefUnit>::REF_UNIT)
+                                .#root_fn()
+                        ),
+                    }
                 }
             }
+        );
+    }
+    code
+}
+
+pub(crate) fn codegen_impl_mul_div_qties(
+    qty_ident: &syn::Ident,
+    derive: &Derive,
+) -> TokenStream {
+    let mut ops: Vec<(&syn::Ident, char, &syn::Ident, &syn::Ident)> = vec![];
+    check_formulas(qty_ident, derive);
+    for derived_as in &derive.derives {
+        for (lhs, op, rhs, res) in consistent_ops(qty_ident, derived_as) {
+            match ops.iter().find(|o| (o.0, o.1, o.2) == (lhs, op, rhs)) {
+                None => ops.push((lhs, op, rhs, res)),
+                Some(known) if known.3 == res => {}
+                Some(known) => abort_call_site!(
+                    "Inconsistent formulas: `{} {} {}` gives `{}` and `{}`.",
+                    lhs, op, rhs, known.3, res
+                ),
+            }
         }
     }
+    let mut code = TokenStream::new();
+    for (lhs_qty_ident, op, rhs_qty_ident, res_qty_ident) in ops {
+        let code_op = match op {
+            '*' if lhs_qty_ident == rhs_qty_ident => {
+                codegen_impl_qty_sqared(res_qty_ident, lhs_qty_ident)
+            }
+            '*' => codegen_impl_qty_mul_qty(
+                res_qty_ident,
+                lhs_qty_ident,
+                rhs_qty_ident,
+            ),
+            _ => codegen_impl_div_qties(
+                res_qty_ident,
+                lhs_qty_ident,
+                rhs_qty_ident,
+            ),
+        };
+        code = quote!(
+            #code
+            #code_op
+        );
+    }
+    code
 }
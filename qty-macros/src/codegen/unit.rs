@@ -52,9 +52,8 @@ pub(crate) fn codegen_unit_constants(
 pub(crate) fn codegen_fn_si_prefix(units: &Vec<UnitDef>) -> TokenStream {
     let mut code = TokenStream::new();
     for unit in units {
-        if unit.si_prefix.is_some() {
+        if let Some(unit_si_prefix) = &unit.si_prefix {
             let unit_ident = &unit.unit_ident;
-            let unit_si_prefix: &syn::Ident = unit.si_prefix.as_ref().unwrap();
             code = quote!(
                 #code
                 Self::#unit_ident =>
@@ -72,15 +71,41 @@ pub(crate) fn codegen_fn_si_prefix(units: &Vec<UnitDef>) -> TokenStream {
     )
 }
 
+pub(crate) fn codegen_fn_binary_prefix(units: &Vec<UnitDef>) -> TokenStream {
+    let mut code = TokenStream::new();
+    for unit in units {
+        if let Some(unit_binary_prefix) = &unit.binary_prefix {
+            let unit_ident = &unit.unit_ident;
+            code = quote!(
+                #code
+                Self::#unit_ident =>
+                    Some(BinaryPrefix::#unit_binary_prefix),
+            )
+        }
+    }
+    // without any binary prefix the default impl of `binary_prefix` is
+    // sufficient
+    if code.is_empty() {
+        return code;
+    }
+    quote!(
+        fn binary_prefix(&self) -> Option<BinaryPrefix> {
+            match self {
+                #code
+                _ => None,
+            }
+        }
+    )
+}
+
 pub(crate) fn codegen_fn_scale(units: &Vec<UnitDef>) -> TokenStream {
     let mut code = TokenStream::new();
     for unit in units {
-        if unit.scale.is_some() {
+        if let Some(unit_scale) = &unit.scale {
             let unit_ident = &unit.unit_ident;
-            let unit_scale: &syn::Lit = unit.scale.as_ref().unwrap();
             code = quote!(
                 #code
-                Self::#unit_ident => #unit_scale as Amount,
+                Self::#unit_ident => Amnt!(#unit_scale),
             )
         } else {
             // should not happen!
@@ -96,23 +121,57 @@ pub(crate) fn codegen_fn_scale(units: &Vec<UnitDef>) -> TokenStream {
     )
 }
 
+pub(crate) fn codegen_fn_offset(units: &Vec<UnitDef>) -> TokenStream {
+    let mut code = TokenStream::new();
+    for unit in units {
+        if let Some(unit_offset) = &unit.offset {
+            let unit_ident = &unit.unit_ident;
+            code = quote!(
+                #code
+                Self::#unit_ident => Amnt!(#unit_offset),
+            )
+        }
+    }
+    // without any offset the default impl of `offset` is sufficient
+    if code.is_empty() {
+        return code;
+    }
+    quote!(
+        fn offset(&self) -> Amount {
+            match self {
+                #code
+                _ => <Amount as QuantityValue>::ZERO,
+            }
+        }
+    )
+}
+
 pub(crate) fn codegen_impl_mul_amnt_unit(
     qty_ident: &syn::Ident,
     unit_enum_ident: &syn::Ident,
 ) -> TokenStream {
+    let mul_value_doc = format!(
+        "Returns `value` * `self`, i.e. a new instance of `{}` (usable in \
+         const contexts, where `value * self` is not).",
+        qty_ident
+    );
     quote!(
-        impl const Mul<#unit_enum_ident> for Amount {
-            type Output = #qty_ident;
+        impl_mul_value_unit!(#qty_ident, #unit_enum_ident);
+        impl<V: QuantityValue> Mul<V> for #unit_enum_ident {
+            type Output = #qty_ident<V>;
             #[inline(always)]
-            fn mul(self, rhs: #unit_enum_ident) -> Self::Output {
-                Self::Output::new(self, rhs)
+            fn mul(self, rhs: V) -> Self::Output {
+                Self::Output::new(rhs, self)
             }
         }
-        impl const Mul<Amount> for #unit_enum_ident {
-            type Output = #qty_ident;
+        impl #unit_enum_ident {
+            #[doc = #mul_value_doc]
             #[inline(always)]
-            fn mul(self, rhs: Amount) -> Self::Output {
-                Self::Output::new(rhs, self)
+            pub const fn mul_value<V: QuantityValue>(
+                self,
+                value: V,
+            ) -> #qty_ident<V> {
+                #qty_ident::new(value, self)
             }
         }
     )
@@ -124,8 +183,27 @@ pub fn codegen_qty_single_unit(
     unit_ident: &syn::Ident,
     unit_name: &syn::LitStr,
     unit_symbol: &syn::LitStr,
+    with_dim: bool,
 ) -> TokenStream {
     let unit_doc = format!("Unit of quantity `{}`.", qty_ident);
+    let new_doc = format!(
+        "Returns a new instance of `{}` (usable in const contexts).",
+        qty_ident
+    );
+    // with a dimension, `Div<Self>` is covered by the dimensional analysis
+    let code_impl_div_self = if with_dim {
+        TokenStream::new()
+    } else {
+        quote!(
+            impl<V: QuantityValue> Div<Self> for #qty_ident<V> {
+                type Output = V;
+                #[inline(always)]
+                fn div(self, rhs: Self) -> Self::Output {
+                    self.value() / rhs.value()
+                }
+            }
+        )
+    };
     quote!(
         #[doc = #unit_doc]
         #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -145,19 +223,27 @@ pub fn codegen_qty_single_unit(
             fn si_prefix(&self) -> Option<SIPrefix> { None }
         }
         #[derive(Copy, Clone, Debug)]
-        pub struct #qty_ident {
-            value: Amount
+        pub struct #qty_ident<V: QuantityValue = Amount> {
+            value: V
+        }
+        impl<V: QuantityValue> #qty_ident<V> {
+            #[doc = #new_doc]
+            #[inline(always)]
+            pub const fn new(value: V, _unit: #unit_enum_ident) -> Self {
+                Self { value }
+            }
         }
-        impl const Quantity for #qty_ident {
+        impl<V: QuantityValue> Quantity for #qty_ident<V> {
+            type Value = V;
             type UnitType = #unit_enum_ident;
 
             #[inline(always)]
-            fn new(value: Amount, _unit: Self::UnitType) -> Self {
+            fn new(value: V, _unit: Self::UnitType) -> Self {
                 Self { value }
             }
 
             #[inline(always)]
-            fn value(&self) -> Amount {
+            fn value(&self) -> V {
                 self.value
             }
 
@@ -166,28 +252,23 @@ pub fn codegen_qty_single_unit(
                 Self::UnitType::#unit_ident
             }
         }
-        impl QuantityImpl for #qty_ident {}
-        impl Add<Self> for #qty_ident {
+        impl<V: QuantityValue> AdditiveQuantity for #qty_ident<V> {}
+        impl<V: QuantityValue> QuantityImpl for #qty_ident<V> {}
+        impl<V: QuantityValue> Add<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn add(self, rhs: Self) -> Self::Output {
                 Self::new(self.value() + rhs.value(), self.unit())
             }
         }
-        impl Sub<Self> for #qty_ident {
+        impl<V: QuantityValue> Sub<Self> for #qty_ident<V> {
             type Output = Self;
             #[inline(always)]
             fn sub(self, rhs: Self) -> Self::Output {
                 Self::new(self.value() - rhs.value(), self.unit())
             }
         }
-        impl Div<Self> for #qty_ident {
-            type Output = Amount;
-            #[inline(always)]
-            fn div(self, rhs: Self) -> Self::Output {
-                self.value() / rhs.value()
-            }
-        }
+        #code_impl_div_self
     )
 }
 
@@ -161,6 +161,12 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
 /// In addition, it creates a constant for each enum variant, thus providing a
 /// constant for each unit.
 ///
+/// Instances of the quantity can be created in const contexts by the
+/// generated const fns `<Unit>::mul_value` and `<Quantity>::new`, for
+/// example
+///
+/// `const MY_WEIGHT: Mass = KILOGRAM.mul_value(Amnt!(47.0));`.
+///
 /// This implies that the identifiers of all units over all defined
 /// quantitities have to be unique!
 ///
@@ -182,24 +188,116 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
 /// `#[unit(<ident>, "<symbol>", <scale>, "<doc>")]`.
 /// `#[unit(<ident>, "<symbol>", <scale>)]`.
 ///
+/// The \<scale\> argument can optionally be followed by an \<offset\>
+/// argument, defining the unit by an affine relation to the reference unit:
+/// `x` * unit == (`x` * \<scale\> + \<offset\>) * reference unit, for
+/// example
+///
+/// `#[unit(Degree_Celsius, "°C", 1.0, 273.15)]`.
+///
+/// Units with SI prefixes can be generated from a unit with SI prefix `NONE`
+/// by appending the argument `prefixes` to the attribute `#[ref_unit]` or
+/// `#[unit]`, giving single SI prefixes or inclusive ranges of them, for
+/// example
+///
+/// `#[ref_unit(Meter, "m", NONE, prefixes = [NANO..=DECI, KILO])]`
+/// `#[unit(Gram, "g", NONE, 0.001, prefixes = [MILLI])]`.
+///
+/// For each prefix, a unit is generated whose identifier, name and symbol
+/// are derived from the prefix and the given unit (i. e. `Nanometer`, "nm"
+/// etc.) and whose scale is the given scale multiplied by the prefix's power
+/// of 10.
+///
+/// Instead of a \<si_prefix\>, a binary prefix (`KIBI` .. `YOBI`) can be
+/// given, and binary prefixes can also be given in argument `prefixes`, for
+/// example
+///
+/// `#[ref_unit(Byte, "B", NONE, prefixes = [KILO..=TERA, KIBI..=TEBI])]`.
+///
 /// To define a quantity without a reference unit, use one ore more unit
 /// attributes in one of the following forms
 ///
 /// `#[unit(<ident>, "<symbol>", "<doc>")]`
 /// `#[unit(<ident>, "<symbol>")]`.
 ///
+/// Besides `Add<Self>`, `Sub<Self>` and the multiplication and division by
+/// a value, the generated quantity implements `Neg`, `Rem<Self>`,
+/// `AddAssign<Self>`, `SubAssign<Self>`, `MulAssign<V>`, `DivAssign<V>` and
+/// `Sum` (for items and references). Summing up an iterator gives a quantity
+/// in the unit of its first item (or zero in the reference unit - if any -
+/// for an empty iterator); use `HasRefUnit::sum_in` to get the sum in a
+/// specific unit. `Product` is not implemented, because the product of two
+/// quantities is not a quantity of the same kind.
+///
+/// To define a derived quantity, give one or more formulas as arguments of
+/// the attribute `#[quantity]`. A formula is a product or quotient of other
+/// quantities, each optionally raised to an integral exponent, for example
+///
+/// `#[quantity(Length * Length)]`
+/// `#[quantity(Length^2 / Time)]`
+/// `#[quantity(Velocity / Time, Length / (Time * Time))]`.
+///
+/// For each formula, impls of `Mul` and `Div` are generated for all pairs of
+/// the quantities named in the formula (incl. the defined quantity) whose
+/// product or quotient has the dimension of one of these quantities.
+///
+/// For a formula giving the quantity as square or cube of another quantity
+/// (like `Length * Length` or `Length^3`), impls of the traits `QtyPow` and
+/// `QtyRoot` are generated in addition, providing typed powers and roots
+/// (like `Length::powi::<2>` and `Area::sqrt`).
+///
+/// In addition, the dimension of the quantity can be given as formula of the
+/// base dimensions `L`, `M`, `T`, `I`, `Th`, `N` and `J` in the argument
+/// `dim`, for example
+///
+/// `#[quantity(dim = L)]`
+/// `#[quantity(Velocity / Time, dim = L / T^2)]`.
+///
+/// Then, the quantity takes part in the dimensional analysis provided by
+/// module `dimension`: instead of the impls for the formulas, generic impls of
+/// `Mul` and `Div` are generated, resolving the result of multiplying or
+/// dividing the quantity by any other value having a dimension to the
+/// quantity registered for the resulting dimension. The given formulas are
+/// only checked at compile time to match the given dimension. In addition,
+/// an impl of `TryFrom<AnyQuantity<V>>` is generated for such a quantity.
+///
+/// A quantity whose instances denote points on a scale rather than amounts,
+/// like absolute temperatures, can be defined by giving the quantity of the
+/// differences between points in the argument `interval`, for example
+///
+/// `#[quantity(interval = TemperatureInterval)]`.
+///
+/// Then, instead of the impls of `Add<Self>`, `Sub<Self>` and the other
+/// arithmetic traits listed above, impls for subtracting two points (giving
+/// an interval) and adding an interval to or subtracting it from a point
+/// (giving a point) are generated, as well as an impl of trait
+/// `PointQuantity`.
+///
 /// # Panics
 ///
 /// The macro panics in the followong cases:
 ///
 /// * Invalid arguments given to the attribute `#[quantity]`.
+/// * A formula consisting of a single quantity with exponent 1 or containing
+///   the defined quantity itself.
+/// * Formulas resulting in inconsistent impls of `Mul` or `Div`.
+/// * A base dimension other than those listed above given in argument `dim`.
+/// * Argument `dim` given for a quantity without a reference unit.
 /// * The given item is not a struct.
 /// * The given struct does have generic parameters and/or fields.
 /// * More than one attribute `#[ref_unit]` is given.
-/// * No attribute `#[unit]` is given.
+/// * No attribute `#[unit]` is given (and no unit is generated from SI
+///   prefixes given to attribute `#[ref_unit]`).
 /// * Wrong number or wrong type of arguments given to attribute `#[ref_unit]`.
 /// * Wrong number of arguments given to an attribute `#[unit]`.
 /// * No \<scale\> argument given to an attribute `#[unit]` when required.
+/// * Argument `dim` given for a quantity having a unit with an offset.
+/// * Argument `prefixes` given for a unit without SI prefix `NONE` or with
+///   an offset, or containing an unknown, empty or repeated prefix or a range
+///   mixing SI and binary prefixes, or generating a unit whose identifier or
+///   symbol is already defined.
+/// * Argument `interval` combined with formulas or argument `dim`, or given
+///   for a quantity without a reference unit.
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn quantity(args: TokenStream, item: TokenStream) -> TokenStream {
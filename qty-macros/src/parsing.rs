@@ -2,10 +2,32 @@ use convert_case::{Case, Casing};
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error::{abort, abort_call_site};
 
+/// Formula defining a quantity as product of integral powers of other
+/// quantities, normalized so that each quantity occurs only once and with a
+/// non-zero exponent.
 pub(crate) struct DerivedAs {
-    pub(crate) lhs_ident: syn::Ident,
-    pub(crate) op: syn::BinOp,
-    pub(crate) rhs_ident: syn::Ident,
+    pub(crate) factors: Vec<(syn::Ident, i32)>,
+}
+
+impl DerivedAs {
+    fn mul(mut self, other: Self, sign: i32) -> Self {
+        for (ident, exp) in other.factors {
+            match self.factors.iter_mut().find(|(i, _)| *i == ident) {
+                Some((_, e)) => *e += sign * exp,
+                None => self.factors.push((ident, sign * exp)),
+            }
+        }
+        self.factors.retain(|(_, e)| *e != 0);
+        self
+    }
+
+    fn pow(mut self, exp: i32) -> Self {
+        for (_, e) in self.factors.iter_mut() {
+            *e *= exp;
+        }
+        self.factors.retain(|(_, e)| *e != 0);
+        self
+    }
 }
 
 pub(crate) struct UnitDef {
@@ -13,8 +35,76 @@ pub(crate) struct UnitDef {
     pub(crate) name: syn::LitStr,
     pub(crate) symbol: syn::LitStr,
     pub(crate) si_prefix: Option<syn::Ident>,
+    pub(crate) binary_prefix: Option<syn::Ident>,
     pub(crate) scale: Option<syn::Lit>,
+    pub(crate) offset: Option<syn::Expr>,
     pub(crate) doc: Option<syn::LitStr>,
+    pub(crate) prefixes: Vec<PrefixRange>,
+}
+
+/// Range of SI or binary prefixes given in argument `prefixes` of attributes
+/// `unit` and `ref_unit` (a single prefix being represented as range with
+/// `end` None).
+pub(crate) struct PrefixRange {
+    start: syn::Ident,
+    end: Option<syn::Ident>,
+}
+
+impl syn::parse::Parse for PrefixRange {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let start: syn::Ident = input.parse()?;
+        let mut end: Option<syn::Ident> = None;
+        if input.peek(syn::Token![..=]) {
+            let _: syn::Token![..=] = input.parse()?;
+            end = Some(input.parse()?);
+        }
+        Ok(PrefixRange { start, end })
+    }
+}
+
+/// The SI prefixes: identifier, name, abbreviation and exponent of base 10.
+const SI_PREFIXES: [(&str, &str, &str, i32); 24] = [
+    ("QUECTO", "Quecto", "q", -30),
+    ("RONTO", "Ronto", "r", -27),
+    ("YOCTO", "Yocto", "y", -24),
+    ("ZEPTO", "Zepto", "z", -21),
+    ("ATTO", "Atto", "a", -18),
+    ("FEMTO", "Femto", "f", -15),
+    ("PICO", "Pico", "p", -12),
+    ("NANO", "Nano", "n", -9),
+    ("MICRO", "Micro", "µ", -6),
+    ("MILLI", "Milli", "m", -3),
+    ("CENTI", "Centi", "c", -2),
+    ("DECI", "Deci", "d", -1),
+    ("DECA", "Deca", "da", 1),
+    ("HECTO", "Hecto", "h", 2),
+    ("KILO", "Kilo", "k", 3),
+    ("MEGA", "Mega", "M", 6),
+    ("GIGA", "Giga", "G", 9),
+    ("TERA", "Tera", "T", 12),
+    ("PETA", "Peta", "P", 15),
+    ("EXA", "Exa", "E", 18),
+    ("ZETTA", "Zetta", "Z", 21),
+    ("YOTTA", "Yotta", "Y", 24),
+    ("RONNA", "Ronna", "R", 27),
+    ("QUETTA", "Quetta", "Q", 30),
+];
+
+/// The binary prefixes: identifier, name, abbreviation and exponent of base 2.
+const BINARY_PREFIXES: [(&str, &str, &str, i32); 8] = [
+    ("KIBI", "Kibi", "Ki", 10),
+    ("MEBI", "Mebi", "Mi", 20),
+    ("GIBI", "Gibi", "Gi", 30),
+    ("TEBI", "Tebi", "Ti", 40),
+    ("PEBI", "Pebi", "Pi", 50),
+    ("EXBI", "Exbi", "Ei", 60),
+    ("ZEBI", "Zebi", "Zi", 70),
+    ("YOBI", "Yobi", "Yi", 80),
+];
+
+#[inline]
+fn is_binary_prefix(ident: &syn::Ident) -> bool {
+    BINARY_PREFIXES.iter().any(|(id, ..)| ident == id)
 }
 
 pub(crate) struct QtyDef {
@@ -37,72 +127,193 @@ impl QtyDef {
 
 pub(crate) type Item = syn::ItemStruct;
 
-#[inline]
-fn get_ident(expr: &syn::Expr) -> Option<&syn::Ident> {
-    match expr {
-        syn::Expr::Path(expr) => expr.path.get_ident(),
-        _ => None,
+pub(crate) struct Derive {
+    pub(crate) derives: Vec<DerivedAs>,
+    pub(crate) dim: Option<DerivedAs>,
+    pub(crate) system: Option<syn::Path>,
+    pub(crate) interval: Option<syn::Ident>,
+}
+
+/// Symbols of the base dimensions, in the order used by type `Dim`.
+pub(crate) const BASE_DIMS: [&str; 7] = ["L", "M", "T", "I", "Th", "N", "J"];
+
+#[rustfmt::skip]
+const ARGS_HELP: &str =
+    "Use `#[quantity]`\n\
+     or  `#[quantity(<formula>, ...)]`,\n\
+     where <formula> is a product or quotient of quantities, optionally \
+     raised to integral exponents,\n\
+     for example `Length * Length`, `Length^2 / Time` or \
+     `Mass * Length^2 / (Time * Time)`,\n\
+     optionally followed by `dim = <formula>`, giving the dimension as \
+     formula of the base dimensions L, M, T, I, Th, N and J, \
+     for example `dim = L / T^2`,\n\
+     and by `system = <type>`, naming the system of quantities the \
+     quantity belongs to,\n\
+     or  `#[quantity(interval = <ident>)]`, naming the quantity of the \
+     differences between the points given by the quantity.";
+
+// <factor> := <ident> | '(' <formula> ')'
+// <term> := <factor> ['^' ['-'] <int>]
+fn parse_term(input: syn::parse::ParseStream) -> syn::Result<DerivedAs> {
+    let factor = if input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in input);
+        let formula = parse_formula(&content)?;
+        if !content.is_empty() {
+            return Err(content.error("Operator '*' or '/' expected."));
+        }
+        formula
+    } else if input.peek(syn::Ident) {
+        DerivedAs { factors: vec![(input.parse::<syn::Ident>()?, 1)] }
+    } else {
+        return Err(input.error("Identifier or '(' expected."));
+    };
+    if input.peek(syn::Token![^]) {
+        input.parse::<syn::Token![^]>()?;
+        let sign = if input.peek(syn::Token![-]) {
+            input.parse::<syn::Token![-]>()?;
+            -1
+        } else {
+            1
+        };
+        let exp = input.parse::<syn::LitInt>()?.base10_parse::<i32>()?;
+        Ok(factor.pow(sign * exp))
+    } else {
+        Ok(factor)
     }
 }
 
-pub(crate) struct Derive {
-    pub(crate) derives: Vec<DerivedAs>
+// <formula> := <term> (('*' | '/') <term>)*
+fn parse_formula(input: syn::parse::ParseStream) -> syn::Result<DerivedAs> {
+    let mut formula = parse_term(input)?;
+    loop {
+        let sign = if input.peek(syn::Token![*]) {
+            input.parse::<syn::Token![*]>()?;
+            1
+        } else if input.peek(syn::Token![/]) {
+            input.parse::<syn::Token![/]>()?;
+            -1
+        } else {
+            break;
+        };
+        formula = formula.mul(parse_term(input)?, sign);
+    }
+    Ok(formula)
 }
 
-impl syn::parse::Parse for Derive {
+impl syn::parse::Parse for DerivedAs {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        const ARGS_ERROR: &str =
-            "Unknown argument(s) given to attribute `quantity`.";
-        const OPERATOR_ERROR: &str = "Binary expression with '*' or '/' expected.";
-        const OPERAND_ERROR: &str = "Identifier expected.";
-        #[rustfmt::skip]
-        const ARGS_HELP: &str =
-            "Use `#[quantity]`\n\
-            or  `#[quantity(<lhs_ident> * <rhs_ident>]`\n\
-            or  `#[quantity(<lhs_ident> / <rhs_ident>]`.";
-
-        let x = syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated(input).unwrap();
-
-        let mut derive = Derive { derives: Vec::with_capacity(x.len()) };
-
-        for expr in x {
-            match expr {
-                syn::Expr::Binary(args) => match args.op {
-                    syn::BinOp::Mul(_) | syn::BinOp::Div(_) => {
-                        let lhs = get_ident(args.left.as_ref());
-                        let rhs = get_ident(args.right.as_ref());
-                        if lhs.is_none() || rhs.is_none() {
-                            abort!(args, OPERAND_ERROR; help = ARGS_HELP)
-                        }
-                        derive.derives.push(DerivedAs {
-                            lhs_ident: lhs.unwrap().clone(),
-                            op: args.op,
-                            rhs_ident: rhs.unwrap().clone(),
-                        });
-                    }
-                    _ => abort!(args, OPERATOR_ERROR; help = ARGS_HELP),
-                },
-                _ => abort!(expr, ARGS_ERROR; help = ARGS_HELP),
+        let formula = parse_formula(input)?;
+        if !input.is_empty() && !input.peek(syn::Token![,]) {
+            return Err(input.error("Operator '*', '/' or '^' expected."));
+        }
+        Ok(formula)
+    }
+}
+
+enum DeriveArg {
+    Formula(DerivedAs),
+    Dim(DerivedAs),
+    System(syn::Path),
+    Interval(syn::Ident),
+}
+
+fn parse_dim(input: syn::parse::ParseStream) -> syn::Result<DerivedAs> {
+    let span = input.span();
+    let dim = input.parse::<DerivedAs>()?;
+    if let Some((ident, _)) = dim
+        .factors
+        .iter()
+        .find(|(ident, _)| !BASE_DIMS.iter().any(|sym| ident == sym))
+    {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "Unknown base dimension `{}`, expected one of {}.",
+                ident,
+                BASE_DIMS.join(", ")
+            ),
+        ));
+    }
+    if dim.factors.is_empty() {
+        return Err(syn::Error::new(span, "Dimension must not be empty."));
+    }
+    Ok(dim)
+}
+
+impl syn::parse::Parse for DeriveArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(syn::Token![=]) {
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+            if key == "dim" {
+                Ok(DeriveArg::Dim(parse_dim(input)?))
+            } else if key == "system" {
+                Ok(DeriveArg::System(input.parse::<syn::Path>()?))
+            } else if key == "interval" {
+                Ok(DeriveArg::Interval(input.parse::<syn::Ident>()?))
+            } else {
+                Err(syn::Error::new(
+                    key.span(),
+                    format!("Unknown argument `{}`.", key),
+                ))
             }
+        } else {
+            Ok(DeriveArg::Formula(input.parse::<DerivedAs>()?))
         }
+    }
+}
 
+impl syn::parse::Parse for Derive {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let args =
+            syn::punctuated::Punctuated::<DeriveArg, syn::Token![,]>
+                ::parse_terminated(input)?;
+        let mut derive = Derive {
+            derives: vec![],
+            dim: None,
+            system: None,
+            interval: None,
+        };
+        for arg in args {
+            match arg {
+                DeriveArg::Formula(formula) => derive.derives.push(formula),
+                DeriveArg::Dim(_) if derive.dim.is_some() => {
+                    return Err(input.error("Argument `dim` given twice."))
+                }
+                DeriveArg::Dim(dim) => derive.dim = Some(dim),
+                DeriveArg::System(_) if derive.system.is_some() => {
+                    return Err(input.error("Argument `system` given twice."))
+                }
+                DeriveArg::System(system) => derive.system = Some(system),
+                DeriveArg::Interval(_) if derive.interval.is_some() => {
+                    return Err(input.error("Argument `interval` given twice."))
+                }
+                DeriveArg::Interval(ident) => derive.interval = Some(ident),
+            }
+        }
+        if derive.system.is_some() && derive.dim.is_none() {
+            return Err(input.error(
+                "Argument `system` requires argument `dim`.",
+            ));
+        }
+        if derive.interval.is_some()
+            && (derive.dim.is_some() || !derive.derives.is_empty())
+        {
+            return Err(input.error(
+                "Argument `interval` can't be combined with formulas or \
+                 argument `dim`.",
+            ));
+        }
         Ok(derive)
     }
 }
 
 pub(crate) fn parse_args(args: TokenStream) -> Option<Derive> {
-    const ARGS_ERROR: &str =
-        "Unknown argument(s) given to attribute `quantity`.";
-    #[rustfmt::skip]
-    const ARGS_HELP: &str =
-        "Use `#[quantity]`\n\
-         or  `#[quantity(<lhs_ident> * <rhs_ident>]`\n\
-         or  `#[quantity(<lhs_ident> / <rhs_ident>]`.";
-
-    if let Ok(derive) = syn::parse::<Derive>(args.into()) {
-        Some(derive)
-    } else {
-        abort_call_site!(ARGS_ERROR; help = ARGS_HELP)
+    match syn::parse2::<Derive>(args) {
+        Ok(derive) => Some(derive),
+        Err(err) => abort!(err.span(), "{}", err; help = ARGS_HELP),
     }
 }
 
@@ -155,15 +366,18 @@ const UNIT_ATTR_HELP: &str =
      or  `#[unit(<ident>, \"<symbol>\", <scale>, \"<doc>\")]`\n\
      or  `#[unit(<ident>, \"<symbol>\", <scale>)]`\n\
      or  `#[unit(<ident>, \"<symbol>\", \"<doc>\")]`\n\
-     or  `#[unit(<ident>, \"<symbol>\")]`.";
+     or  `#[unit(<ident>, \"<symbol>\")]`,\n\
+     where <scale> can optionally be followed by an <offset>, and the args\n\
+     can be followed by `prefixes = [<si_prefix>..=<si_prefix>]`.";
+
+const NO_UNIT_ATTR_ERROR: &str =
+    "At least one unit description must be given via attribute `unit`.";
 
 fn get_unit_attrs(
     attrs: &Vec<syn::Attribute>,
 ) -> (Vec<syn::Attribute>, Option<syn::Attribute>) {
     const MORE_THAN_ONE_REFUNIT_ATTR_ERROR: &str =
         "There can only be one `refunit` attribute.";
-    const NO_UNIT_ATTR_ERROR: &str =
-        "At least one unit description must be given via attribute `unit`.";
 
     let mut unit_attrs: Vec<syn::Attribute> = vec![];
     let mut opt_ref_unit_attr: Option<syn::Attribute> = None;
@@ -177,7 +391,9 @@ fn get_unit_attrs(
             opt_ref_unit_attr = Some(attr.clone());
         }
     }
-    if unit_attrs.is_empty() {
+    // With a reference unit given, units may also be generated from SI
+    // prefixes, so this case is checked after expansion.
+    if unit_attrs.is_empty() && opt_ref_unit_attr.is_none() {
         abort_call_site!(NO_UNIT_ATTR_ERROR; help = UNIT_ATTR_HELP);
     }
     (unit_attrs, opt_ref_unit_attr)
@@ -193,8 +409,14 @@ impl syn::parse::Parse for UnitDef {
             return Err(syn::Error::new(input.span(), ARGS_LIST_ERROR));
         }
         let mut si_prefix: Option<syn::Ident> = None;
-        if input.peek(syn::Ident) {
-            si_prefix = Some(input.parse::<syn::Ident>()?);
+        let mut binary_prefix: Option<syn::Ident> = None;
+        if input.peek(syn::Ident) && !input.peek2(syn::Token![=]) {
+            let prefix = input.parse::<syn::Ident>()?;
+            if is_binary_prefix(&prefix) {
+                binary_prefix = Some(prefix);
+            } else {
+                si_prefix = Some(prefix);
+            }
             let opt_comma: Option<syn::Token![,]> = input.parse()?;
             if opt_comma.is_none() && !input.is_empty() {
                 return Err(syn::Error::new(input.span(), ARGS_LIST_ERROR));
@@ -208,9 +430,46 @@ impl syn::parse::Parse for UnitDef {
                 return Err(syn::Error::new(input.span(), ARGS_LIST_ERROR));
             }
         };
+        let mut offset: Option<syn::Expr> = None;
+        if scale.is_some()
+            && (input.peek(syn::LitFloat)
+                || input.peek(syn::LitInt)
+                || input.peek(syn::Token![-]))
+        {
+            let neg: Option<syn::Token![-]> = input.parse()?;
+            let lit: syn::Lit = input.parse()?;
+            offset = Some(syn::parse_quote!(#neg #lit));
+            let opt_comma: Option<syn::Token![,]> = input.parse()?;
+            if opt_comma.is_none() && !input.is_empty() {
+                return Err(syn::Error::new(input.span(), ARGS_LIST_ERROR));
+            }
+        };
         let mut doc: Option<syn::LitStr> = None;
         if input.peek(syn::LitStr) {
             doc = Some(input.parse::<syn::LitStr>()?);
+            let opt_comma: Option<syn::Token![,]> = input.parse()?;
+            if opt_comma.is_none() && !input.is_empty() {
+                return Err(syn::Error::new(input.span(), ARGS_LIST_ERROR));
+            }
+        }
+        let mut prefixes: Vec<PrefixRange> = vec![];
+        if input.peek(syn::Ident) && input.peek2(syn::Token![=]) {
+            let key: syn::Ident = input.parse()?;
+            if key != "prefixes" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("Unknown argument `{}`.", key),
+                ));
+            }
+            let _: syn::Token![=] = input.parse()?;
+            let content;
+            syn::bracketed!(content in input);
+            prefixes = syn::punctuated::Punctuated::<
+                PrefixRange,
+                syn::Token![,],
+            >::parse_terminated(&content)?
+            .into_iter()
+            .collect();
         }
         // Check if input is exhausted:
         if !input.is_empty() {
@@ -229,8 +488,11 @@ impl syn::parse::Parse for UnitDef {
             name,
             symbol,
             si_prefix,
+            binary_prefix,
             scale,
+            offset,
             doc,
+            prefixes,
         })
     }
 }
@@ -244,7 +506,8 @@ fn ref_unit_def_from_attr(ref_unit_attr: &syn::Attribute) -> UnitDef {
         "Use `#[ref_unit(<ident>, \"<symbol>\", <si_prefix>, \"<doc>\")]`\n\
          or  `#[ref_unit(<ident>, \"<symbol>\", <si_prefix>)]`\n\
          or  `#[ref_unit(<ident>, \"<symbol>\", \"<doc>\")]`\n\
-         or  `#[ref_unit(<ident>, \"<symbol>\")]`.";
+         or  `#[ref_unit(<ident>, \"<symbol>\")]`,\n\
+         optionally followed by `prefixes = [<si_prefix>..=<si_prefix>]`.";
 
     match ref_unit_attr.parse_args::<UnitDef>() {
         Ok(mut unit_def) => {
@@ -267,14 +530,16 @@ fn unit_defs_with_scale_from_attrs(
     attrs: &Vec<syn::Attribute>,
 ) -> Vec<UnitDef> {
     const WRONG_NUMBER_OF_ARGS_ERROR: &str =
-        "3, 4 or 5 comma-separated args expected.";
+        "3, 4, 5 or 6 comma-separated args expected.";
     const NO_SCALE_ERROR: &str = "<scale> arg expected.";
     #[rustfmt::skip]
     const HELP: &str =
         "Use `#[unit(<ident>, \"<symbol>\", <si_prefix>, <scale>, \"<doc>\")]`
          or  `#[unit(<ident>, \"<symbol>\", <si_prefix>, <scale>)]`\n\
          or  `#[unit(<ident>, \"<symbol>\", <scale>, \"<doc>\")]`\n\
-         or  `#[unit(<ident>, \"<symbol>\", <scale>)]`.";
+         or  `#[unit(<ident>, \"<symbol>\", <scale>)]`,\n\
+         where <scale> can optionally be followed by an <offset>, and the\n\
+         args can be followed by `prefixes = [<si_prefix>..=<si_prefix>]`.";
 
     let mut unit_defs: Vec<UnitDef> = vec![];
     for attr in attrs {
@@ -307,7 +572,11 @@ fn unit_defs_without_scale_from_attrs(
     for attr in attrs {
         match attr.parse_args::<UnitDef>() {
             Ok(unit_def) => {
-                if unit_def.scale.is_some() || unit_def.si_prefix.is_some() {
+                if unit_def.scale.is_some()
+                    || unit_def.si_prefix.is_some()
+                    || unit_def.binary_prefix.is_some()
+                    || !unit_def.prefixes.is_empty()
+                {
                     abort!(attr, WRONG_NUMBER_OF_ARGS_ERROR; help = HELP);
                 }
                 unit_defs.push(unit_def);
@@ -329,6 +598,240 @@ pub(crate) fn opt_lit_to_f64(lit: &Option<syn::Lit>) -> f64 {
     }
 }
 
+/// Returns the decimal literal `digits` multiplied by 10^`exp`, as float
+/// literal without exponent.
+fn shift_decimal(digits: &str, exp: i32) -> String {
+    let (mantissa, mut exp) = match digits.split_once(['e', 'E']) {
+        Some((mantissa, lit_exp)) => {
+            (mantissa, exp + lit_exp.parse::<i32>().unwrap())
+        }
+        None => (digits, exp),
+    };
+    let (int_part, frac_part) =
+        mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let all_digits = format!("{}{}", int_part, frac_part);
+    exp += int_part.len() as i32;
+    let (int_part, frac_part) = if exp <= 0 {
+        let zeros = "0".repeat(exp.unsigned_abs() as usize);
+        (String::from("0"), format!("{}{}", zeros, all_digits))
+    } else if exp as usize >= all_digits.len() {
+        let zeros = "0".repeat(exp as usize - all_digits.len());
+        (format!("{}{}", all_digits, zeros), String::new())
+    } else {
+        let (int_part, frac_part) = all_digits.split_at(exp as usize);
+        (int_part.to_string(), frac_part.to_string())
+    };
+    let int_part = int_part.trim_start_matches('0');
+    let frac_part = frac_part.trim_end_matches('0');
+    format!(
+        "{}.{}",
+        if int_part.is_empty() { "0" } else { int_part },
+        if frac_part.is_empty() { "0" } else { frac_part }
+    )
+}
+
+/// Returns the decimal literal `digits` multiplied by `factor`, as float
+/// literal without exponent.
+fn mul_decimal(digits: &str, factor: u128) -> String {
+    let (mantissa, exp) = match digits.split_once(['e', 'E']) {
+        Some((mantissa, lit_exp)) => {
+            (mantissa, lit_exp.parse::<i32>().unwrap())
+        }
+        None => (digits, 0),
+    };
+    let (int_part, frac_part) =
+        mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let coeff = format!("{}{}", int_part, frac_part)
+        .parse::<u128>()
+        .ok()
+        .and_then(|coeff| coeff.checked_mul(factor));
+    match coeff {
+        Some(coeff) => {
+            shift_decimal(&coeff.to_string(), exp - frac_part.len() as i32)
+        }
+        None => abort_call_site!("Scale of prefixed unit out of range."),
+    }
+}
+
+/// A SI or binary prefix: identifier, name, abbreviation and exponent (of
+/// base 10 or 2, resp.).
+type Prefix = (&'static str, &'static str, &'static str, i32);
+
+/// Returns the SI prefixes and the binary prefixes given by `ranges`, each in
+/// ascending order.
+fn prefixes_from_ranges(ranges: &[PrefixRange]) -> (Vec<Prefix>, Vec<Prefix>) {
+    let loc_of = |ident: &syn::Ident| {
+        if let Some(idx) = SI_PREFIXES.iter().position(|(id, ..)| ident == id)
+        {
+            (false, idx)
+        } else if let Some(idx) =
+            BINARY_PREFIXES.iter().position(|(id, ..)| ident == id)
+        {
+            (true, idx)
+        } else {
+            abort!(
+                ident,
+                "Unknown SI or binary prefix `{}`.", ident;
+                help = "Use one of {}.", SI_PREFIXES
+                    .iter()
+                    .chain(BINARY_PREFIXES.iter())
+                    .map(|(id, ..)| *id)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    };
+    let mut locs: Vec<(bool, usize)> = vec![];
+    for range in ranges {
+        let (binary, start) = loc_of(&range.start);
+        let end = match range.end.as_ref().map(loc_of) {
+            None => start,
+            Some((end_binary, end)) if end_binary == binary => end,
+            Some(_) => abort!(
+                range.start,
+                "Range mixing SI prefixes and binary prefixes."
+            ),
+        };
+        if end < start {
+            abort!(range.start, "Empty range of prefixes.");
+        }
+        let table: &[Prefix] = if binary {
+            &BINARY_PREFIXES
+        } else {
+            &SI_PREFIXES
+        };
+        for (idx, (prefix_ident, ..)) in
+            table.iter().enumerate().take(end + 1).skip(start)
+        {
+            if locs.contains(&(binary, idx)) {
+                abort!(
+                    range.start,
+                    "Prefix `{}` given more than once.", prefix_ident
+                );
+            }
+            locs.push((binary, idx));
+        }
+    }
+    locs.sort_unstable();
+    let (binary_locs, si_locs): (Vec<_>, Vec<_>) =
+        locs.into_iter().partition(|(binary, _)| *binary);
+    (
+        si_locs.into_iter().map(|(_, idx)| SI_PREFIXES[idx]).collect(),
+        binary_locs
+            .into_iter()
+            .map(|(_, idx)| BINARY_PREFIXES[idx])
+            .collect(),
+    )
+}
+
+/// Returns the units generated from the SI or binary prefixes given in
+/// `unit_def`.
+fn prefixed_unit_defs(unit_def: &UnitDef) -> Vec<UnitDef> {
+    if unit_def.prefixes.is_empty() {
+        return vec![];
+    }
+    match &unit_def.si_prefix {
+        Some(si_prefix) if si_prefix == "NONE" => {}
+        _ => abort!(
+            unit_def.unit_ident,
+            "Argument `prefixes` requires SI prefix `NONE`."
+        ),
+    }
+    if unit_def.offset.is_some() {
+        abort!(
+            unit_def.unit_ident,
+            "Argument `prefixes` can't be combined with an offset."
+        );
+    }
+    let scale_digits = match unit_def.scale.as_ref().unwrap() {
+        syn::Lit::Float(f) => f.base10_digits().to_string(),
+        syn::Lit::Int(i) => i.base10_digits().to_string(),
+        lit => abort!(lit, "Internal error: unexspected non-numeric literal."),
+    };
+    let base_ident = unit_def.unit_ident.to_string();
+    let base_name = unit_def.name.value();
+    let base_symbol = unit_def.symbol.value();
+    let lower_first = |s: &str| {
+        let mut chars = s.chars();
+        chars.next().map_or(String::new(), |c| {
+            c.to_lowercase().chain(chars).collect()
+        })
+    };
+    let span = unit_def.unit_ident.span();
+    let prefixed_unit_def =
+        |(prefix_ident, prefix_name, abbr, _): Prefix,
+         factor: &str,
+         scale: &str| {
+            let prefix = Some(syn::Ident::new(prefix_ident, span));
+            let is_binary = is_binary_prefix(prefix.as_ref().unwrap());
+            UnitDef {
+                unit_ident: syn::Ident::new(
+                    &format!("{}{}", prefix_name, lower_first(&base_ident)),
+                    span,
+                ),
+                name: syn::LitStr::new(
+                    &format!("{}{}", prefix_name, lower_first(&base_name)),
+                    span,
+                ),
+                symbol: syn::LitStr::new(
+                    &format!("{}{}", abbr, base_symbol),
+                    span,
+                ),
+                si_prefix: if is_binary { None } else { prefix.clone() },
+                binary_prefix: if is_binary { prefix } else { None },
+                scale: Some(syn::Lit::Float(syn::LitFloat::new(scale, span))),
+                offset: None,
+                doc: Some(syn::LitStr::new(
+                    &format!("{}·{}", factor, base_symbol),
+                    span,
+                )),
+                prefixes: vec![],
+            }
+        };
+    let (si_prefixes, binary_prefixes) =
+        prefixes_from_ranges(&unit_def.prefixes);
+    let mut unit_defs: Vec<UnitDef> = vec![];
+    for prefix in si_prefixes {
+        let exp = prefix.3;
+        unit_defs.push(prefixed_unit_def(
+            prefix,
+            shift_decimal("1", exp).trim_end_matches(".0"),
+            &shift_decimal(&scale_digits, exp),
+        ));
+    }
+    for prefix in binary_prefixes {
+        let factor = 1_u128 << prefix.3;
+        unit_defs.push(prefixed_unit_def(
+            prefix,
+            &factor.to_string(),
+            &mul_decimal(&scale_digits, factor),
+        ));
+    }
+    unit_defs
+}
+
+/// Adds the units generated from the prefixes given in `unit_defs`.
+fn add_prefixed_unit_defs(unit_defs: &mut Vec<UnitDef>) {
+    let mut prefixed: Vec<UnitDef> = vec![];
+    for unit_def in unit_defs.iter() {
+        for prefixed_def in prefixed_unit_defs(unit_def) {
+            if unit_defs.iter().chain(prefixed.iter()).any(|def| {
+                def.unit_ident == prefixed_def.unit_ident
+                    || def.symbol.value() == prefixed_def.symbol.value()
+            }) {
+                abort!(
+                    unit_def.unit_ident,
+                    "Prefixed unit `{}` (\"{}\") already defined.",
+                    prefixed_def.unit_ident,
+                    prefixed_def.symbol.value()
+                );
+            }
+            prefixed.push(prefixed_def);
+        }
+    }
+    unit_defs.append(&mut prefixed);
+}
+
 pub(crate) fn analyze(item_ast: &mut Item) -> QtyDef {
     check_struct(item_ast);
     let attrs = &mut item_ast.attrs;
@@ -340,6 +843,10 @@ pub(crate) fn analyze(item_ast: &mut Item) -> QtyDef {
         qty_def.ref_unit_ident = Some(ref_unit_def.unit_ident.clone());
         qty_def.units = unit_defs_with_scale_from_attrs(&unit_attrs);
         qty_def.units.insert(0, ref_unit_def);
+        add_prefixed_unit_defs(&mut qty_def.units);
+        if qty_def.units.len() < 2 {
+            abort_call_site!(NO_UNIT_ATTR_ERROR; help = UNIT_ATTR_HELP);
+        }
         qty_def.units.sort_by(|a, b| {
             let x = opt_lit_to_f64(&a.scale);
             let y = opt_lit_to_f64(&b.scale);
@@ -347,9 +854,7 @@ pub(crate) fn analyze(item_ast: &mut Item) -> QtyDef {
         });
     } else {
         qty_def.units = unit_defs_without_scale_from_attrs(&unit_attrs);
-        qty_def
-            .units
-            .sort_by(|a, b| a.name.value().cmp(&b.name.value()));
+        qty_def.units.sort_by_key(|a| a.name.value());
     }
     qty_def
 }
\ No newline at end of file
@@ -0,0 +1,434 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
+
+use crate::{
+    dimension::{DynDim, HasDimension, QuantityOfDim},
+    parse, Amount, ParseQuantityError, QuantityValue, UnitExpr, AMNT_ONE,
+};
+
+/// An error which can be returned when combining or converting instances of
+/// `AnyQuantity`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DimensionError {
+    /// The dimensions of the operands (or of the value to be converted and
+    /// the target type) differ.
+    Mismatch(DynDim, DynDim),
+    /// An exponent of the resulting dimension is out of range.
+    Overflow,
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch(lhs, rhs) => {
+                write!(form, "dimensions '{lhs}' and '{rhs}' differ")
+            }
+            Self::Overflow => {
+                fmt::Display::fmt("exponent of dimension out of range", form)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DimensionError {}
+
+/// A quantity whose dimension is only known at runtime.
+///
+/// An instance of `AnyQuantity` holds a value, a dimension and the scale to be
+/// applied to the value in order to get the value in terms of the reference
+/// unit of the quantity having that dimension.
+///
+/// Instances can be created from the quantities taking part in the
+/// dimensional analysis (see module [dimension](crate::dimension)) via
+/// `From` and converted back via `TryFrom`, or parsed from a string holding a
+/// number followed by a unit expression like "kg·m/s²" (see [UnitExpr]).
+///
+/// Adding or subtracting instances with different dimensions fails with
+/// `DimensionError::Mismatch`, so these operations, as well as multiplication
+/// and division, return a `Result`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "velocity")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::velocity::{Velocity, METER_PER_SECOND};
+///
+/// let dist: AnyQuantity = "3 km".parse().unwrap();
+/// let dur: AnyQuantity = "10 min".parse().unwrap();
+/// let speed = (dist / dur).unwrap();
+/// let speed = Velocity::try_from(speed).unwrap();
+/// assert_eq!(speed, Amnt!(5.0) * METER_PER_SECOND);
+/// assert_eq!(
+///     dist + dur,
+///     Err(DimensionError::Mismatch(dist.dim(), dur.dim()))
+/// );
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AnyQuantity<V: QuantityValue = Amount> {
+    value: V,
+    dim: DynDim,
+    scale: Amount,
+}
+
+impl<V: QuantityValue> AnyQuantity<V> {
+    /// Returns a new instance of `AnyQuantity`.
+    pub const fn new(value: V, dim: DynDim, scale: Amount) -> Self {
+        Self { value, dim, scale }
+    }
+
+    /// Returns the numerical part of `self`.
+    pub const fn value(&self) -> V {
+        self.value
+    }
+
+    /// Returns the dimension of `self`.
+    #[must_use]
+    pub const fn dim(&self) -> DynDim {
+        self.dim
+    }
+
+    /// Returns the factor to be applied to the value of `self` in order to
+    /// get the value in terms of the reference unit.
+    #[must_use]
+    #[allow(clippy::double_must_use)]
+    pub const fn scale(&self) -> Amount {
+        self.scale
+    }
+
+    /// Returns the value of `self` in terms of the reference unit.
+    pub fn ref_value(&self) -> V {
+        if self.scale == AMNT_ONE {
+            self.value
+        } else {
+            self.value.mul_amount(self.scale)
+        }
+    }
+
+    /// Returns the value of `other` in terms of the scale of `self`.
+    fn equiv_value(&self, other: &Self) -> V {
+        if self.scale == other.scale {
+            other.value
+        } else {
+            other.value.mul_amount(other.scale / self.scale)
+        }
+    }
+
+    /// Returns an instance of `Q` equivalent to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DimensionError::Mismatch` if the dimension of `self` differs
+    /// from the dimension of `Q`.
+    pub fn downcast<Q>(self) -> Result<Q, DimensionError>
+    where
+        Q: HasDimension<Value = V>,
+        Q::System: QuantityOfDim<Q::Dim, Qty<V> = Q>,
+    {
+        let dim = DynDim::of::<Q::Dim>();
+        if self.dim == dim {
+            Ok(<Q::System as QuantityOfDim<Q::Dim>>::from_value_and_scale(
+                self.value, self.scale,
+            ))
+        } else {
+            Err(DimensionError::Mismatch(self.dim, dim))
+        }
+    }
+}
+
+impl<Q: HasDimension> From<Q> for AnyQuantity<Q::Value> {
+    fn from(qty: Q) -> Self {
+        let (value, scale) = qty.value_and_scale();
+        Self::new(value, DynDim::of::<Q::Dim>(), scale)
+    }
+}
+
+impl<V: QuantityValue + FromStr> FromStr for AnyQuantity<V> {
+    type Err = ParseQuantityError;
+
+    /// Parses a number optionally followed by a unit expression (see
+    /// [UnitExpr]). A number without a unit gives a dimensionless instance.
+    fn from_str(lit: &str) -> Result<Self, Self::Err> {
+        let (num, sym) = parse::split_qty_lit(lit)?;
+        let value = num
+            .parse::<V>()
+            .map_err(|_| ParseQuantityError::InvalidNumber)?;
+        if sym.is_empty() {
+            return Ok(Self::new(value, DynDim::ONE, AMNT_ONE));
+        }
+        let expr = sym
+            .parse::<UnitExpr>()
+            .map_err(|_| ParseQuantityError::UnknownUnit)?;
+        Ok(Self::new(value, expr.dim(), expr.scale()))
+    }
+}
+
+impl<V: QuantityValue> PartialEq for AnyQuantity<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dim == other.dim && self.value == self.equiv_value(other)
+    }
+}
+
+impl<V: QuantityValue> PartialOrd for AnyQuantity<V> {
+    /// Returns the partial order of `self`s and `other`s values, if both have
+    /// the same dimension, otherwise `None`.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.dim == other.dim {
+            self.value.partial_cmp(&self.equiv_value(other))
+        } else {
+            None
+        }
+    }
+}
+
+impl<V: QuantityValue> fmt::Display for AnyQuantity<V> {
+    /// Formats `self` as its value in terms of the reference unit followed by
+    /// its dimension, for example "5 L·T^-1".
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.ref_value(), form)?;
+        if self.dim != DynDim::ONE {
+            write!(form, " {}", self.dim)?;
+        }
+        Ok(())
+    }
+}
+
+impl<V: QuantityValue> Add for AnyQuantity<V> {
+    type Output = Result<Self, DimensionError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.dim != rhs.dim {
+            return Err(DimensionError::Mismatch(self.dim, rhs.dim));
+        }
+        let value = self.value + self.equiv_value(&rhs);
+        Ok(Self::new(value, self.dim, self.scale))
+    }
+}
+
+impl<V: QuantityValue> Sub for AnyQuantity<V> {
+    type Output = Result<Self, DimensionError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.dim != rhs.dim {
+            return Err(DimensionError::Mismatch(self.dim, rhs.dim));
+        }
+        let value = self.value - self.equiv_value(&rhs);
+        Ok(Self::new(value, self.dim, self.scale))
+    }
+}
+
+impl<V: QuantityValue> Mul for AnyQuantity<V> {
+    type Output = Result<Self, DimensionError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let dim = self
+            .dim
+            .checked_mul(rhs.dim)
+            .ok_or(DimensionError::Overflow)?;
+        Ok(Self::new(self.value * rhs.value, dim, self.scale * rhs.scale))
+    }
+}
+
+impl<V: QuantityValue> Div for AnyQuantity<V> {
+    type Output = Result<Self, DimensionError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let dim = self
+            .dim
+            .checked_div(rhs.dim)
+            .ok_or(DimensionError::Overflow)?;
+        Ok(Self::new(self.value / rhs.value, dim, self.scale / rhs.scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use alloc::format;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct Test;
+
+    #[quantity(dim = L, system = Test)]
+    #[ref_unit(Meter, "m", NONE)]
+    #[unit(Kilometer, "km", KILO, 1000)]
+    struct Distance {}
+
+    #[quantity(dim = T, system = Test)]
+    #[ref_unit(Second, "s", NONE)]
+    #[unit(Minute, "min", 60)]
+    struct Duration {}
+
+    #[quantity(Distance / Duration, dim = L / T, system = Test)]
+    #[ref_unit(Meter_per_Second, "m/s", NONE)]
+    #[unit(Kilometer_per_Second, "km/s", 1000)]
+    struct Speed {}
+
+    #[test]
+    fn test_from_and_try_from() {
+        let d = AnyQuantity::from(Amnt!(3.0) * KILOMETER);
+        assert_eq!(d.dim(), DynDim([1, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(d.value(), Amnt!(3.0));
+        assert_eq!(d.scale(), Amnt!(1000.0));
+        assert_eq!(d.ref_value(), Amnt!(3000.0));
+        assert_eq!(Distance::try_from(d), Ok(Amnt!(3.0) * KILOMETER));
+        let t = AnyQuantity::from(Amnt!(2.0) * MINUTE);
+        assert_eq!(Duration::try_from(t), Ok(Amnt!(2.0) * MINUTE));
+        assert_eq!(
+            Duration::try_from(d),
+            Err(DimensionError::Mismatch(d.dim(), t.dim()))
+        );
+        assert_eq!(
+            Distance::try_from(t),
+            Err(DimensionError::Mismatch(t.dim(), d.dim()))
+        );
+        let x = AnyQuantity::from(Amnt!(7.0));
+        assert_eq!(x.dim(), DynDim::ONE);
+        assert_eq!(
+            Distance::try_from(x),
+            Err(DimensionError::Mismatch(DynDim::ONE, d.dim()))
+        );
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let d1 = AnyQuantity::from(Amnt!(1.0) * KILOMETER);
+        let d2 = AnyQuantity::from(Amnt!(500.0) * METER);
+        let sum = (d1 + d2).unwrap();
+        assert_eq!(sum.value(), Amnt!(1.5));
+        assert_eq!(sum.scale(), Amnt!(1000.0));
+        assert_eq!(
+            (d2 - d1).unwrap(),
+            AnyQuantity::from(Amnt!(-500.0) * METER)
+        );
+        let t = AnyQuantity::from(Amnt!(1.0) * SECOND);
+        assert_eq!(d1 + t, Err(DimensionError::Mismatch(d1.dim(), t.dim())));
+        assert_eq!(t - d1, Err(DimensionError::Mismatch(t.dim(), d1.dim())));
+        let x = AnyQuantity::from(Amnt!(1.0));
+        assert_eq!(
+            x + d1,
+            Err(DimensionError::Mismatch(DynDim::ONE, d1.dim()))
+        );
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let d = AnyQuantity::from(Amnt!(30.0) * METER);
+        let t = AnyQuantity::from(Amnt!(6.0) * SECOND);
+        let v = (d / t).unwrap();
+        assert_eq!(Speed::try_from(v), Ok(Amnt!(5.0) * METER_PER_SECOND));
+        assert_eq!(
+            Distance::try_from(v),
+            Err(DimensionError::Mismatch(v.dim(), d.dim()))
+        );
+        assert_eq!(
+            Distance::try_from((v * t).unwrap()),
+            Ok(Amnt!(30.0) * METER)
+        );
+        assert_eq!((d / d).unwrap(), AnyQuantity::from(Amnt!(1.0)));
+        let dim = DynDim([127, 0, 0, 0, 0, 0, 0]);
+        let x = AnyQuantity::new(Amnt!(1.0), dim, AMNT_ONE);
+        assert_eq!((x * d).unwrap_err(), DimensionError::Overflow);
+        let dim = DynDim([-128, 0, 0, 0, 0, 0, 0]);
+        let x = AnyQuantity::new(Amnt!(1.0), dim, AMNT_ONE);
+        assert_eq!((x / d).unwrap_err(), DimensionError::Overflow);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let d1 = AnyQuantity::from(Amnt!(1.0) * KILOMETER);
+        let d2 = AnyQuantity::from(Amnt!(999.0) * METER);
+        assert!(d1 > d2);
+        assert_eq!(d1, AnyQuantity::from(Amnt!(1000.0) * METER));
+        let t = AnyQuantity::from(Amnt!(1.0) * SECOND);
+        assert!(d1.partial_cmp(&t).is_none());
+        assert_ne!(d1, t);
+    }
+
+    #[test]
+    fn test_parse_dimensionless() {
+        let x: AnyQuantity = "7".parse().unwrap();
+        assert_eq!(x, AnyQuantity::from(Amnt!(7.0)));
+        assert_eq!(
+            "7 parsec".parse::<AnyQuantity>(),
+            Err(ParseQuantityError::UnknownUnit)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_display() {
+        let d = AnyQuantity::from(Amnt!(3.0) * KILOMETER);
+        assert_eq!(format!("{d:.0}"), "3000 L");
+        let v = AnyQuantity::from(Amnt!(5.0) * METER_PER_SECOND);
+        assert_eq!(format!("{v:.1}"), "5.0 L·T^-1");
+        assert_eq!(format!("{:.0}", AnyQuantity::from(Amnt!(7.0))), "7");
+    }
+
+    #[cfg(all(feature = "power", feature = "velocity"))]
+    #[test]
+    fn test_si_units() {
+        use crate::si::{
+            energy::{Energy, KILOWATTHOUR},
+            power::{Power, KILOWATT, WATT},
+            time::{Time, HOUR},
+            velocity::Velocity,
+        };
+
+        let p: AnyQuantity = "2.5 kW".parse().unwrap();
+        assert_eq!(p.dim(), DynDim([2, 1, -3, 0, 0, 0, 0]));
+        #[cfg(feature = "alloc")]
+        assert_eq!(format!("{p:.0}"), "2500 L^2·M·T^-3");
+        assert_eq!(Power::try_from(p), Ok(Amnt!(2.5) * KILOWATT));
+        assert_eq!(p, AnyQuantity::from(Amnt!(2500.0) * WATT));
+        let t = AnyQuantity::from(Amnt!(2.0) * HOUR);
+        let e = Energy::try_from((p * t).unwrap()).unwrap();
+        assert_eq!(e, Amnt!(5.0) * KILOWATTHOUR);
+        assert_eq!((t / t).unwrap(), AnyQuantity::from(Amnt!(1.0)));
+        let v: AnyQuantity = "5 m/s".parse().unwrap();
+        assert!(Velocity::try_from(v).is_ok());
+        assert_eq!(
+            Time::try_from(v),
+            Err(DimensionError::Mismatch(v.dim(), t.dim()))
+        );
+        assert_eq!(
+            (p + t).unwrap_err(),
+            DimensionError::Mismatch(p.dim(), t.dim())
+        );
+        assert!((p - t).is_err());
+        let p2 = (p + AnyQuantity::from(Amnt!(500.0) * WATT)).unwrap();
+        assert_eq!(p2, "3 kW".parse().unwrap());
+        assert!(p2 > p);
+        assert!(t.partial_cmp(&p).is_none());
+        let p3: AnyQuantity = "3000 kg·m²·s⁻³".parse().unwrap();
+        assert_eq!(p3, p2);
+        let dimless: AnyQuantity = "7".parse().unwrap();
+        assert_eq!(dimless.dim(), DynDim::ONE);
+        assert_eq!(
+            "7 parsec".parse::<AnyQuantity>(),
+            Err(ParseQuantityError::UnknownUnit)
+        );
+        let dim = DynDim([127, 0, 0, 0, 0, 0, 0]);
+        let x = AnyQuantity::new(Amnt!(1.0), dim, Amnt!(1.0));
+        assert_eq!((x * x).unwrap_err(), DimensionError::Overflow);
+    }
+}
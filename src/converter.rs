@@ -9,7 +9,7 @@
 
 //! this module contains the converter trait for quantities
 
-use crate::{Amount, Quantity};
+use crate::{Quantity, QuantityValue};
 
 /// Trait for quantity converters
 pub trait Converter<Q: Quantity> {
@@ -24,25 +24,153 @@ pub trait Converter<Q: Quantity> {
 /// Each entry of the table is holding the following elements:
 /// * from_unit: Q::UnitType,
 /// * to_unit: Q::UnitType,
-/// * factor: AmountT,
-/// * offset: AmountT
+/// * factor: Q::Value,
+/// * offset: Q::Value
+///
 /// defining the conversion
 /// to_amount = from_amount * factor + offset
+///
+/// The table doesn't need to hold an entry for each pair of units: each entry
+/// can also be applied in reverse, i.e. as
+/// from_amount = (to_amount - offset) / factor,
+/// and conversions are chained through intermediate units, if there is no
+/// entry connecting `from_unit` and `to_unit` directly.
+///
+/// # Example
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::{ConversionTable, Converter};
+/// #[quantity]
+/// #[unit(Kelvin, "K")]
+/// #[unit(Celsius, "°C")]
+/// #[unit(Fahrenheit, "°F")]
+/// struct Temperature {}
+///
+/// const CONVERTER: ConversionTable<Temperature, 2> = ConversionTable {
+///     mappings: [
+///         (KELVIN, CELSIUS, Amnt!(1.0), Amnt!(-273.15)),
+///         (CELSIUS, FAHRENHEIT, Amnt!(1.8), Amnt!(32.0)),
+///     ],
+/// };
+///
+/// let t = Amnt!(50.0) * FAHRENHEIT;
+/// let c = CONVERTER.convert(&t, CELSIUS).unwrap();
+/// assert_eq!(c, Amnt!(10.0) * CELSIUS);
+/// ```
 #[derive(Debug)]
 pub struct ConversionTable<Q: Quantity, const N: usize> {
     /// Table of tuples (from_unit, to_unit, factor, offset), defining the
     /// conversion to_amount = from_amount * factor + offset
-    pub mappings: [(Q::UnitType, Q::UnitType, Amount, Amount); N],
+    #[allow(clippy::type_complexity)]
+    pub mappings: [(Q::UnitType, Q::UnitType, Q::Value, Q::Value); N],
+}
+
+impl<Q: Quantity, const N: usize> ConversionTable<Q, N> {
+    // Depth-first search for a chain of mappings leading from `unit` to
+    // `to_unit`, applying the mappings to `amount` on the way. Each mapping is
+    // visited at most once, so the search terminates even if the table
+    // contains cycles.
+    fn search(
+        &self,
+        amount: Q::Value,
+        unit: Q::UnitType,
+        to_unit: Q::UnitType,
+        visited: &mut [bool; N],
+    ) -> Option<Q::Value> {
+        if unit == to_unit {
+            return Some(amount);
+        }
+        for (idx, (from, to, factor, offset)) in
+            self.mappings.iter().enumerate()
+        {
+            if visited[idx] {
+                continue;
+            }
+            let (next_amount, next_unit) = if *from == unit {
+                (amount * *factor + *offset, *to)
+            } else if *to == unit && *factor != Q::Value::ZERO {
+                ((amount - *offset) / *factor, *from)
+            } else {
+                continue;
+            };
+            visited[idx] = true;
+            let res = self.search(next_amount, next_unit, to_unit, visited);
+            if res.is_some() {
+                return res;
+            }
+        }
+        None
+    }
 }
 
 impl<Q: Quantity, const N: usize> Converter<Q> for ConversionTable<Q, N> {
     fn convert(self, qty: &Q, to_unit: Q::UnitType) -> Option<Q> {
-        if (*qty).unit() == to_unit {
-            return Some(*qty);
-        }
-        self.mappings.iter().find_map(|(from, to, factor, offset)| {
-            (*from == (*qty).unit() && *to == to_unit)
-                .then(|| Q::new(qty.value() * factor + offset, to_unit))
-        })
+        let mut visited = [false; N];
+        self.search(qty.value(), qty.unit(), to_unit, &mut visited)
+            .map(|amount| Q::new(amount, to_unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[unit(Alpha, "a")]
+    #[unit(Beta, "b")]
+    #[unit(Gamma, "c")]
+    #[unit(Delta, "d")]
+    #[unit(Epsilon, "e")]
+    struct Level {}
+
+    // b = 2·a + 1, c = 4·b, a = c / 8 - 0.5 (closing a cycle), e = 5
+    const CONVERTER: ConversionTable<Level, 4> = ConversionTable {
+        mappings: [
+            (ALPHA, BETA, Amnt!(2.0), Amnt!(1.0)),
+            (BETA, GAMMA, Amnt!(4.0), Amnt!(0.0)),
+            (GAMMA, ALPHA, Amnt!(0.125), Amnt!(-0.5)),
+            (ALPHA, EPSILON, Amnt!(0.0), Amnt!(5.0)),
+        ],
+    };
+
+    #[test]
+    fn test_identity() {
+        let a = Amnt!(3.0) * ALPHA;
+        assert_eq!(CONVERTER.convert(&a, ALPHA), Some(a));
+        // even without any mapping for the unit
+        let d = Amnt!(3.0) * DELTA;
+        assert_eq!(CONVERTER.convert(&d, DELTA), Some(d));
+    }
+
+    #[test]
+    fn test_direct_and_reverse() {
+        let a = Amnt!(3.0) * ALPHA;
+        let b = CONVERTER.convert(&a, BETA).unwrap();
+        assert_eq!(b, Amnt!(7.0) * BETA);
+        assert_eq!(CONVERTER.convert(&b, ALPHA), Some(a));
+    }
+
+    #[test]
+    fn test_chained() {
+        let a = Amnt!(3.0) * ALPHA;
+        assert_eq!(CONVERTER.convert(&a, GAMMA), Some(Amnt!(28.0) * GAMMA));
+        let c = Amnt!(28.0) * GAMMA;
+        assert_eq!(CONVERTER.convert(&c, BETA), Some(Amnt!(7.0) * BETA));
+        let e = CONVERTER.convert(&(Amnt!(7.0) * BETA), EPSILON);
+        assert_eq!(e, Some(Amnt!(5.0) * EPSILON));
+    }
+
+    #[test]
+    fn test_no_path() {
+        // the search terminates although the table contains a cycle
+        let a = Amnt!(3.0) * ALPHA;
+        assert!(CONVERTER.convert(&a, DELTA).is_none());
+        let d = Amnt!(3.0) * DELTA;
+        assert!(CONVERTER.convert(&d, ALPHA).is_none());
+        // a mapping with factor 0 can't be applied in reverse
+        let e = Amnt!(5.0) * EPSILON;
+        assert!(CONVERTER.convert(&e, ALPHA).is_none());
     }
 }
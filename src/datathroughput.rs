@@ -0,0 +1,64 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! Definition of derived quantity `DataThroughput` (DataVolume / Time).
+
+use crate::{datavolume::DataVolume, prelude::*, si::time::Time};
+
+#[quantity(DataVolume / Time)]
+#[ref_unit(
+    Byte_per_Second,
+    "B/s",
+    NONE,
+    "Reference unit of quantity `DataThroughput`",
+    prefixes = [KILO..=TERA, KIBI..=TEBI]
+)]
+#[unit(Bit_per_Second, "bit/s", 0.125, "bit/s")]
+#[unit(Kilobit_per_Second, "kbit/s", 125, "kbit/s")]
+#[unit(Megabit_per_Second, "Mbit/s", 125000, "Mbit/s")]
+#[unit(Gigabit_per_Second, "Gbit/s", 125000000, "Gbit/s")]
+#[unit(Terabit_per_Second, "Tbit/s", 125000000000., "Tbit/s")]
+/// The amount of digital information transferred per time unit.
+///
+/// # Example
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::datathroughput::{DataThroughput, MEGABYTE_PER_SECOND};
+/// # use quantities::datavolume::{DataVolume, GIGABYTE, MEGABYTE};
+/// # use quantities::si::time::{Time, MINUTE};
+/// let rate: DataThroughput = (Amnt!(6.) * GIGABYTE) / (Amnt!(1.) * MINUTE);
+/// assert_eq!(rate, Amnt!(100.) * MEGABYTE_PER_SECOND);
+/// let vol: DataVolume = rate * (Amnt!(30.) * MINUTE);
+/// assert_eq!(vol, Amnt!(180.) * GIGABYTE);
+/// ```
+pub struct DataThroughput;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        datavolume::{GIBIBYTE, KILOBYTE, MEGABIT},
+        si::time::{MILLISECOND, SECOND},
+    };
+
+    #[test]
+    fn data_throughput() {
+        let rate: DataThroughput =
+            (Amnt!(250.) * MEGABIT) / (Amnt!(2.) * SECOND);
+        assert_eq!(rate, Amnt!(125.) * MEGABIT_PER_SECOND);
+        assert_eq!(rate.unit(), MEGABIT_PER_SECOND);
+        let rate: DataThroughput =
+            (Amnt!(3.) * KILOBYTE) / (Amnt!(2.) * MILLISECOND);
+        assert_eq!(rate, Amnt!(1.5) * MEGABYTE_PER_SECOND);
+        let t: Time =
+            (Amnt!(2.) * GIBIBYTE) / (Amnt!(512.) * MEBIBYTE_PER_SECOND);
+        assert_eq!(t, Amnt!(4.) * SECOND);
+    }
+}
@@ -0,0 +1,56 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! Definition of basic quantity `DataVolume`.
+
+use crate::prelude::*;
+
+#[quantity]
+#[ref_unit(
+    Byte,
+    "B",
+    NONE,
+    "Reference unit of quantity `DataVolume`",
+    prefixes = [KILO..=EXA, KIBI..=EXBI]
+)]
+#[unit(Bit, "bit", 0.125, "0.125·B")]
+#[unit(Kilobit, "kbit", 125, "1000·bit")]
+#[unit(Megabit, "Mbit", 125000, "1000000·bit")]
+#[unit(Gigabit, "Gbit", 125000000, "1000000000·bit")]
+#[unit(Terabit, "Tbit", 125000000000., "1000000000000·bit")]
+/// The amount of digital information.
+///
+/// Units with SI prefixes and units with binary prefixes are defined for
+/// `Byte`, so that conversions between both are available:
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::datavolume::{DataVolume, GIBIBYTE, MEGABYTE};
+/// let vol = Amnt!(2.) * GIBIBYTE;
+/// assert_eq!(vol.convert(MEGABYTE), Amnt!(2147.483648) * MEGABYTE);
+/// ```
+pub struct DataVolume;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_volume() {
+        assert_eq!(Amnt!(8.) * BIT, Amnt!(1.) * BYTE);
+        assert_eq!(Amnt!(1.) * MEBIBYTE, Amnt!(1024.) * KIBIBYTE);
+        assert_eq!(
+            (Amnt!(1.) * KIBIBYTE).convert(KILOBYTE),
+            Amnt!(1.024) * KILOBYTE
+        );
+        assert!(Amnt!(1.) * GIBIBYTE > Amnt!(1.) * GIGABYTE);
+        assert_eq!(KIBIBYTE.binary_prefix(), Some(BinaryPrefix::KIBI));
+        assert_eq!(<DataVolume>::unit_from_symbol("MiB"), Some(MEBIBYTE));
+    }
+}
@@ -0,0 +1,736 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! Compile-time dimensional analysis.
+//!
+//! The dimension of a quantity is given as product of powers of the seven
+//! base dimensions of the International System of Quantities (ISQ):
+//!
+//! | Symbol | Base dimension            |
+//! |--------|---------------------------|
+//! | `L`    | length                    |
+//! | `M`    | mass                      |
+//! | `T`    | time                      |
+//! | `I`    | electric current          |
+//! | `Th`   | thermodynamic temperature |
+//! | `N`    | amount of substance       |
+//! | `J`    | luminous intensity        |
+//!
+//! It is represented by the type [Dim], taking the exponents of the base
+//! dimensions as type-level integers.
+//!
+//! A quantity defined by the proc-macro `quantity` takes part in the
+//! dimensional analysis, if its dimension is given as argument `dim` of the
+//! attribute, for example `#[quantity(dim = L / T^2)]`. For such quantities,
+//! products and quotients with other quantities having a dimension (incl. the
+//! numerical types, which are dimensionless) are resolved to the quantity
+//! registered for the dimension of the result. Multiplying or dividing
+//! quantities whose result has a dimension not registered for a quantity
+//! fails to compile. Rates don't have a dimension: multiplying or dividing
+//! such a quantity by a [Rate](crate::Rate) results in the term or the per
+//! quantity of the rate, whether or not that quantity has a dimension.
+//!
+//! Quantities are registered per system of quantities, which can be any type
+//! defined in the package defining the quantities and is given as argument
+//! `system` of the attribute, for example
+//! `#[quantity(dim = L, system = Astro)]`. It defaults to [SI], the system of
+//! the quantities defined in this package. Because each dimension can only be
+//! registered for one quantity per system, at most one quantity per dimension
+//! and system can take part in the dimensional analysis. The result of a
+//! product or quotient is looked up in the system of the left operand.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "volume")]
+//! # {
+//! use quantities::prelude::*;
+//! use quantities::si::{
+//!     length::{Length, METER},
+//!     volume::{Volume, CUBIC_METER},
+//! };
+//!
+//! let l: Length = Amnt!(2.) * METER;
+//! let v: Volume = l * l * l;
+//! assert_eq!(v, Amnt!(8.) * CUBIC_METER);
+//! assert_eq!(v / l / l, l);
+//! # }
+//! ```
+//!
+//! There's no quantity registered for the dimension L⁴, so the following
+//! fails to compile:
+//!
+//! ```compile_fail
+//! use quantities::prelude::*;
+//! use quantities::si::length::{Length, METER};
+//!
+//! let l: Length = Amnt!(2.) * METER;
+//! let x = l * l * l * l;
+//! ```
+
+use core::{fmt, marker::PhantomData, ops::Div};
+
+#[cfg(feature = "fpdec")]
+use crate::Decimal;
+use crate::{
+    Amount, HasRefUnit, LinearScaledUnit, Quantity, QuantityValue, Rate,
+    AMNT_ONE,
+};
+
+/// Type-level integer.
+pub trait Integer {
+    /// The value represented by `Self`.
+    const VALUE: i8;
+}
+
+/// Type-level integer 0.
+#[derive(Copy, Clone, Debug)]
+pub struct Z0;
+
+/// Type-level integer `I` + 1, where `I` is not negative.
+#[derive(Copy, Clone, Debug)]
+pub struct P<I>(PhantomData<I>);
+
+/// Type-level integer `I` - 1, where `I` is not positive.
+#[derive(Copy, Clone, Debug)]
+pub struct N<I>(PhantomData<I>);
+
+impl Integer for Z0 {
+    const VALUE: i8 = 0;
+}
+
+impl<I: Integer> Integer for P<I> {
+    const VALUE: i8 = I::VALUE + 1;
+}
+
+impl<I: Integer> Integer for N<I> {
+    const VALUE: i8 = I::VALUE - 1;
+}
+
+/// Addition of type-level integers.
+pub trait IntAdd<Rhs> {
+    /// The sum `Self` + `Rhs`.
+    type Output;
+}
+
+impl<I> IntAdd<I> for Z0 {
+    type Output = I;
+}
+
+impl<I> IntAdd<Z0> for P<I> {
+    type Output = Self;
+}
+
+impl<I> IntAdd<Z0> for N<I> {
+    type Output = Self;
+}
+
+// (a + 1) + (b + 1) = (a + (b + 1)) + 1
+impl<A: IntAdd<P<B>>, B> IntAdd<P<B>> for P<A> {
+    type Output = P<A::Output>;
+}
+
+// (a - 1) + (b - 1) = (a + (b - 1)) - 1
+impl<A: IntAdd<N<B>>, B> IntAdd<N<B>> for N<A> {
+    type Output = N<A::Output>;
+}
+
+// (a + 1) + (b - 1) = a + b
+impl<A: IntAdd<B>, B> IntAdd<N<B>> for P<A> {
+    type Output = A::Output;
+}
+
+// (a - 1) + (b + 1) = a + b
+impl<A: IntAdd<B>, B> IntAdd<P<B>> for N<A> {
+    type Output = A::Output;
+}
+
+/// Negation of type-level integers.
+pub trait IntNeg {
+    /// The negation of `Self`.
+    type Output;
+}
+
+impl IntNeg for Z0 {
+    type Output = Self;
+}
+
+impl<I: IntNeg> IntNeg for P<I> {
+    type Output = N<I::Output>;
+}
+
+impl<I: IntNeg> IntNeg for N<I> {
+    type Output = P<I::Output>;
+}
+
+/// Subtraction of type-level integers.
+pub trait IntSub<Rhs> {
+    /// The difference `Self` - `Rhs`.
+    type Output;
+}
+
+impl<A, B> IntSub<B> for A
+where
+    B: IntNeg,
+    A: IntAdd<B::Output>,
+{
+    type Output = <A as IntAdd<B::Output>>::Output;
+}
+
+/// Dimension given by the exponents of the seven base dimensions.
+#[derive(Copy, Clone, Debug)]
+#[allow(clippy::type_complexity)]
+pub struct Dim<L, M, T, I, Th, N, J>(PhantomData<(L, M, T, I, Th, N, J)>);
+
+/// The dimension of dimensionless values.
+pub type One = Dim<Z0, Z0, Z0, Z0, Z0, Z0, Z0>;
+
+/// Abstract type of dimensions.
+pub trait Dimension {
+    /// The exponents of the base dimensions L, M, T, I, Th, N and J.
+    const EXPONENTS: [i8; 7];
+}
+
+impl<L, M, T, I, Th, N, J> Dimension for Dim<L, M, T, I, Th, N, J>
+where
+    L: Integer,
+    M: Integer,
+    T: Integer,
+    I: Integer,
+    Th: Integer,
+    N: Integer,
+    J: Integer,
+{
+    const EXPONENTS: [i8; 7] = [
+        L::VALUE,
+        M::VALUE,
+        T::VALUE,
+        I::VALUE,
+        Th::VALUE,
+        N::VALUE,
+        J::VALUE,
+    ];
+}
+
+/// Dimension determined at runtime, given by the exponents of the base
+/// dimensions L, M, T, I, Th, N and J.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DynDim(pub [i8; 7]);
+
+impl DynDim {
+    /// The dimension of dimensionless values.
+    pub const ONE: Self = Self([0; 7]);
+
+    /// Returns the runtime representation of the dimension `D`.
+    #[must_use]
+    pub const fn of<D: Dimension>() -> Self {
+        Self(D::EXPONENTS)
+    }
+
+    /// Returns the dimension of the product of values with dimensions `self`
+    /// and `rhs`, or `None` if an exponent overflows.
+    #[must_use]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let mut exps = self.0;
+        for (exp, rhs_exp) in exps.iter_mut().zip(rhs.0) {
+            *exp = exp.checked_add(rhs_exp)?;
+        }
+        Some(Self(exps))
+    }
+
+    /// Returns the dimension of the quotient of values with dimensions `self`
+    /// and `rhs`, or `None` if an exponent overflows.
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let mut exps = self.0;
+        for (exp, rhs_exp) in exps.iter_mut().zip(rhs.0) {
+            *exp = exp.checked_sub(rhs_exp)?;
+        }
+        Some(Self(exps))
+    }
+}
+
+impl fmt::Display for DynDim {
+    /// Formats `self` as product of powers of the base dimensions, for
+    /// example "L·T^-2", or as "1" if `self` is dimensionless.
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SYMBOLS: [&str; 7] = ["L", "M", "T", "I", "Th", "N", "J"];
+        if *self == Self::ONE {
+            return fmt::Display::fmt("1", form);
+        }
+        let mut sep = "";
+        for (sym, exp) in SYMBOLS.iter().zip(self.0) {
+            match exp {
+                0 => continue,
+                1 => write!(form, "{sep}{sym}")?,
+                _ => write!(form, "{sep}{sym}^{exp}")?,
+            }
+            sep = "·";
+        }
+        Ok(())
+    }
+}
+
+/// The dimension of the product of quantities with dimensions `Self` and
+/// `Rhs`.
+pub trait DimMul<Rhs> {
+    /// Dimension of the product.
+    type Output;
+}
+
+/// The dimension of the quotient of quantities with dimensions `Self` and
+/// `Rhs`.
+pub trait DimDiv<Rhs> {
+    /// Dimension of the quotient.
+    type Output;
+}
+
+macro_rules! impl_dim_op {
+    ($dim_op:ident, $int_op:ident) => {
+        impl<L1, M1, T1, I1, Th1, N1, J1, L2, M2, T2, I2, Th2, N2, J2>
+            $dim_op<Dim<L2, M2, T2, I2, Th2, N2, J2>>
+            for Dim<L1, M1, T1, I1, Th1, N1, J1>
+        where
+            L1: $int_op<L2>,
+            M1: $int_op<M2>,
+            T1: $int_op<T2>,
+            I1: $int_op<I2>,
+            Th1: $int_op<Th2>,
+            N1: $int_op<N2>,
+            J1: $int_op<J2>,
+        {
+            type Output = Dim<
+                L1::Output,
+                M1::Output,
+                T1::Output,
+                I1::Output,
+                Th1::Output,
+                N1::Output,
+                J1::Output,
+            >;
+        }
+    };
+}
+
+impl_dim_op!(DimMul, IntAdd);
+impl_dim_op!(DimDiv, IntSub);
+
+/// Types of values having a dimension.
+pub trait HasDimension {
+    /// The dimension of `Self`.
+    type Dim: Dimension;
+    /// The type of the numerical part of `Self`.
+    type Value: QuantityValue;
+    /// The system of quantities `Self` belongs to.
+    type System;
+
+    /// Returns the numerical part of `self` and the factor to be applied to
+    /// it in order to get the numerical part in terms of the reference unit.
+    fn value_and_scale(&self) -> (Self::Value, Amount);
+}
+
+/// The system of quantities defined in this package.
+#[derive(Copy, Clone, Debug)]
+pub struct SI;
+
+/// Registers the quantity `Qty<V>` for the dimension `D` in the system of
+/// quantities `Self`.
+pub trait QuantityOfDim<D> {
+    /// The quantity registered for `D`.
+    type Qty<V: QuantityValue>;
+
+    /// Returns an instance of `Self::Qty<V>` equivalent to `value` * `scale`
+    /// in terms of its reference unit, using the unit with the given scale,
+    /// if there is one, otherwise a unit fitting to the resulting value.
+    fn from_value_and_scale<V: QuantityValue>(
+        value: V,
+        scale: Amount,
+    ) -> Self::Qty<V>;
+}
+
+impl<S> QuantityOfDim<One> for S {
+    type Qty<V: QuantityValue> = V;
+
+    #[inline(always)]
+    fn from_value_and_scale<V: QuantityValue>(value: V, scale: Amount) -> V {
+        value.mul_amount(scale)
+    }
+}
+
+/// Product of values having a dimension, resulting in the quantity registered
+/// for the dimension of the product in the system of quantities of `Self`.
+pub trait QtyMul<Rhs> {
+    /// Resulting quantity.
+    type Output;
+
+    /// Returns the product `self` * `rhs`.
+    fn qty_mul(self, rhs: Rhs) -> Self::Output;
+}
+
+impl<A, B> QtyMul<B> for A
+where
+    A: HasDimension,
+    B: HasDimension<Value = A::Value>,
+    A::Dim: DimMul<B::Dim>,
+    A::System: QuantityOfDim<<A::Dim as DimMul<B::Dim>>::Output>,
+{
+    type Output = <A::System as QuantityOfDim<
+        <A::Dim as DimMul<B::Dim>>::Output,
+    >>::Qty<A::Value>;
+
+    #[inline]
+    fn qty_mul(self, rhs: B) -> Self::Output {
+        let (lhs_value, lhs_scale) = self.value_and_scale();
+        let (rhs_value, rhs_scale) = rhs.value_and_scale();
+        <A::System as QuantityOfDim<
+            <A::Dim as DimMul<B::Dim>>::Output,
+        >>::from_value_and_scale(
+            lhs_value * rhs_value,
+            lhs_scale * rhs_scale,
+        )
+    }
+}
+
+/// Quotient of values having a dimension, resulting in the quantity
+/// registered for the dimension of the quotient in the system of quantities
+/// of `Self`.
+pub trait QtyDiv<Rhs> {
+    /// Resulting quantity.
+    type Output;
+
+    /// Returns the quotient `self` / `rhs`.
+    fn qty_div(self, rhs: Rhs) -> Self::Output;
+}
+
+impl<A, B> QtyDiv<B> for A
+where
+    A: HasDimension,
+    B: HasDimension<Value = A::Value>,
+    A::Dim: DimDiv<B::Dim>,
+    A::System: QuantityOfDim<<A::Dim as DimDiv<B::Dim>>::Output>,
+{
+    type Output = <A::System as QuantityOfDim<
+        <A::Dim as DimDiv<B::Dim>>::Output,
+    >>::Qty<A::Value>;
+
+    #[inline]
+    fn qty_div(self, rhs: B) -> Self::Output {
+        let (lhs_value, lhs_scale) = self.value_and_scale();
+        let (rhs_value, rhs_scale) = rhs.value_and_scale();
+        <A::System as QuantityOfDim<
+            <A::Dim as DimDiv<B::Dim>>::Output,
+        >>::from_value_and_scale(
+            lhs_value / rhs_value,
+            lhs_scale / rhs_scale,
+        )
+    }
+}
+
+impl<T: HasDimension> HasDimension for &T {
+    type Dim = T::Dim;
+    type Value = T::Value;
+    type System = T::System;
+
+    #[inline(always)]
+    fn value_and_scale(&self) -> (Self::Value, Amount) {
+        (*self).value_and_scale()
+    }
+}
+
+macro_rules! impl_dimensionless {
+    ($($t:ty),*) => {
+        $(
+        impl HasDimension for $t {
+            type Dim = One;
+            type Value = Self;
+            type System = ();
+
+            #[inline(always)]
+            fn value_and_scale(&self) -> (Self, Amount) {
+                (*self, AMNT_ONE)
+            }
+        }
+        )*
+    };
+}
+
+impl_dimensionless!(f32, f64, i32, i64);
+#[cfg(feature = "fpdec")]
+impl_dimensionless!(Decimal);
+
+// Rates don't have a dimension, so that multiplying or dividing a quantity by
+// a rate does not depend on the term quantity having a dimension.
+impl<A, TQ> QtyMul<Rate<TQ, A>> for A
+where
+    A: Quantity + Div<A, Output = A::Value>,
+    TQ: Quantity<Value = A::Value>,
+{
+    type Output = TQ;
+
+    #[inline]
+    fn qty_mul(self, rhs: Rate<TQ, A>) -> TQ {
+        rhs * self
+    }
+}
+
+impl<A, PQ> QtyDiv<Rate<A, PQ>> for A
+where
+    A: Quantity + Div<A, Output = A::Value>,
+    PQ: Quantity<Value = A::Value>,
+{
+    type Output = PQ;
+
+    #[inline]
+    fn qty_div(self, rhs: Rate<A, PQ>) -> PQ {
+        rhs.reciprocal() * self
+    }
+}
+
+/// Returns `(qty.value(), qty.unit().scale())`.
+#[doc(hidden)]
+#[inline(always)]
+pub fn value_and_scale<Q>(qty: &Q) -> (Q::Value, Amount)
+where
+    Q: Quantity,
+    Q::UnitType: LinearScaledUnit,
+{
+    (qty.value(), qty.unit().scale())
+}
+
+/// Returns an instance of `Q` equivalent to `value` * `scale` in terms of
+/// its reference unit.
+#[doc(hidden)]
+pub fn from_value_and_scale<Q>(value: Q::Value, scale: Amount) -> Q
+where
+    Q: HasRefUnit,
+    Q::UnitType: LinearScaledUnit,
+{
+    match Q::UnitType::from_scale(scale) {
+        Some(unit) => Q::new(value, unit),
+        None => Q::_fit(value.mul_amount(scale)),
+    }
+}
+
+/// Marker trait implemented by a dimension for itself only.
+#[diagnostic::on_unimplemented(
+    message = "the dimensions `{Self}` and `{D}` differ",
+    label = "formula does not match the dimension of the quantity"
+)]
+pub trait SameDimension<D> {}
+
+impl<D> SameDimension<D> for D {}
+
+/// Fails to compile unless `A` and `B` are the same dimension.
+#[doc(hidden)]
+pub const fn assert_same_dimension<A: SameDimension<B>, B>() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct Test;
+
+    #[quantity(dim = L, system = Test)]
+    #[ref_unit(Meter, "m", NONE)]
+    #[unit(Centimeter, "cm", CENTI, 0.01)]
+    #[unit(Kilometer, "km", KILO, 1000)]
+    struct Length {}
+
+    #[quantity(dim = M, system = Test)]
+    #[ref_unit(Gram, "g", NONE)]
+    #[unit(Kilogram, "kg", 1000)]
+    struct Weight {}
+
+    #[quantity(dim = T, system = Test)]
+    #[ref_unit(Second, "s", NONE)]
+    #[unit(Minute, "min", 60)]
+    #[unit(Hour, "h", 3600)]
+    struct Time {}
+
+    #[quantity(Length * Length, dim = L^2, system = Test)]
+    #[ref_unit(Square_Meter, "m²", NONE)]
+    #[unit(Square_Kilometer, "km²", MEGA, 1000000)]
+    struct Area {}
+
+    #[quantity(Length^3, dim = L^3, system = Test)]
+    #[ref_unit(Cubic_Meter, "m³", NONE)]
+    #[unit(Liter, "l", 0.001)]
+    struct Volume {}
+
+    #[quantity(Length / Time, dim = L / T, system = Test)]
+    #[ref_unit(Meter_per_Second, "m/s", NONE)]
+    #[unit(Kilometer_per_Hour, "km/h", 0.2777777777777778)]
+    struct Velocity {}
+
+    #[quantity(Velocity / Time, dim = L / T^2, system = Test)]
+    #[ref_unit(Meter_per_Second_squared, "m/s²", NONE)]
+    #[unit(Kilometer_per_Second_squared, "km/s²", KILO, 1000)]
+    struct Acceleration {}
+
+    #[quantity(dim = T^-1, system = Test)]
+    #[ref_unit(Hertz, "Hz", NONE)]
+    #[unit(Kilohertz, "kHz", KILO, 1000)]
+    struct Frequency {}
+
+    #[quantity]
+    #[ref_unit(Euro, "EUR", NONE)]
+    #[unit(Cent, "ct", 0.01)]
+    struct Money {}
+
+    #[test]
+    fn test_exponents() {
+        assert_eq!(One::EXPONENTS, [0; 7]);
+        assert_eq!(
+            <Weight as HasDimension>::Dim::EXPONENTS,
+            [0, 1, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            <Acceleration as HasDimension>::Dim::EXPONENTS,
+            [1, 0, -2, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            <Frequency as HasDimension>::Dim::EXPONENTS,
+            [0, 0, -1, 0, 0, 0, 0]
+        );
+        assert_eq!(<Amount as HasDimension>::Dim::EXPONENTS, [0; 7]);
+    }
+
+    #[test]
+    fn test_qty_mul_div() {
+        let l = Amnt!(2.) * METER;
+        let v: Volume = l * l * (Amnt!(3.) * METER);
+        assert_eq!(v, Amnt!(12.) * CUBIC_METER);
+        assert_eq!(v / l / l, Amnt!(3.) * METER);
+        assert_eq!(l * l, Amnt!(4.) * SQUARE_METER);
+        let s: Velocity = (Amnt!(12.) * METER) / (Amnt!(4.) * SECOND);
+        assert_eq!(s, Amnt!(3.) * METER_PER_SECOND);
+        let acc: Acceleration = s / (Amnt!(1.5) * SECOND);
+        assert_eq!(acc, Amnt!(2.) * METER_PER_SECOND_SQUARED);
+        assert_eq!(acc * (Amnt!(1.5) * SECOND), s);
+        assert_eq!(s / acc, Amnt!(1.5) * SECOND);
+        let w: Weight = Amnt!(3.) * KILOGRAM;
+        assert_eq!(w.qty_mul(Amnt!(2.)), Amnt!(6.) * KILOGRAM);
+    }
+
+    #[test]
+    fn test_resulting_unit() {
+        // unit of the result given by the product of the scales
+        let a = (Amnt!(2.) * KILOMETER) * (Amnt!(3.) * KILOMETER);
+        assert_eq!(a.unit(), SQUARE_KILOMETER);
+        assert_eq!(a.value(), Amnt!(6.));
+        // no unit with the resulting scale => equivalent value
+        let a = (Amnt!(2.) * KILOMETER) * (Amnt!(50.) * CENTIMETER);
+        assert_eq!(a, Amnt!(1000.) * SQUARE_METER);
+        let k = Amnt!(3.) * KILOMETER * Amnt!(2.);
+        assert_eq!(k.unit(), KILOMETER);
+        assert_eq!((k / Amnt!(4.)).value(), Amnt!(1.5));
+    }
+
+    #[test]
+    fn test_dimensionless_results() {
+        let l = Amnt!(2.) * METER;
+        let r: Amount = l / (Amnt!(50.) * CENTIMETER);
+        assert_eq!(r, Amnt!(4.));
+        let f = Amnt!(5.) * HERTZ;
+        assert_eq!(f * (Amnt!(2.) * SECOND), Amnt!(10.));
+        assert_eq!((Amnt!(2.) * MINUTE) * f, Amnt!(600.));
+        assert_eq!(
+            (Amnt!(3.) * KILOHERTZ) * (Amnt!(2.) * MINUTE),
+            Amnt!(360000.)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_refs() {
+        let l = Amnt!(2.) * METER;
+        let t = Amnt!(4.) * SECOND;
+        assert_eq!(&l * &l, Amnt!(4.) * SQUARE_METER);
+        assert_eq!(&l / t, Amnt!(0.5) * METER_PER_SECOND);
+        assert_eq!(l / &t, Amnt!(0.5) * METER_PER_SECOND);
+        assert_eq!(&l / &l, Amnt!(1.));
+    }
+
+    #[test]
+    fn test_rates() {
+        let pace: Rate<Time, Length> =
+            Rate::new(Amnt!(5.), MINUTE, Amnt!(1.), KILOMETER);
+        let t: Time = Amnt!(10.) * KILOMETER * pace;
+        assert_eq!(t, Amnt!(50.) * MINUTE);
+        assert_eq!(t.unit(), MINUTE);
+        let d: Length = t / pace;
+        assert_eq!(d, Amnt!(10.) * KILOMETER);
+    }
+
+    #[test]
+    fn test_formulas() {
+        #[quantity]
+        #[ref_unit(Meter, "m", NONE)]
+        #[unit(Kilometer, "km", KILO, 1000)]
+        struct Dist {}
+
+        #[quantity]
+        #[ref_unit(Second, "s", NONE)]
+        #[unit(Hour, "h", 3600)]
+        struct Dur {}
+
+        #[quantity]
+        #[ref_unit(Kilogram, "kg", KILO)]
+        #[unit(Gram, "g", NONE, 0.001)]
+        struct Weight {}
+
+        #[quantity(Dist^2)]
+        #[ref_unit(Square_Meter, "m²", NONE)]
+        #[unit(Square_Kilometer, "km²", MEGA, 1000000)]
+        struct Surface {}
+
+        #[quantity(Dist / Dur)]
+        #[ref_unit(Meter_per_Second, "m/s", NONE)]
+        #[unit(Kilometer_per_Hour, "km/h", 0.2777777777777778)]
+        struct Speed {}
+
+        #[quantity(Speed * Dur^-1, Dist / (Dur * Dur))]
+        #[ref_unit(Meter_per_Second_squared, "m/s²", NONE)]
+        #[unit(Kilometer_per_Second_squared, "km/s²", KILO, 1000)]
+        struct Accel {}
+
+        #[quantity(Weight * Dist^2 / (Dur * Dur), Weight * (Dist / Dur)^2)]
+        #[ref_unit(Joule, "J", NONE)]
+        #[unit(Kilojoule, "kJ", KILO, 1000)]
+        struct Work {}
+
+        let s: Surface = (Amnt!(2.) * KILOMETER) * (Amnt!(3.) * KILOMETER);
+        assert_eq!(s, Amnt!(6.) * SQUARE_KILOMETER);
+        assert_eq!(s / (Amnt!(2.) * KILOMETER), Amnt!(3.) * KILOMETER);
+        let v: Speed = (Amnt!(12.) * METER) / (Amnt!(1.) * SECOND);
+        let t = Amnt!(4.) * SECOND;
+        let a: Accel = v / t;
+        assert_eq!(a, Amnt!(3.) * METER_PER_SECOND_SQUARED);
+        assert_eq!(a * t, v);
+        assert_eq!(t * a, v);
+        assert_eq!(v / a, t);
+        assert_eq!(Work::new(Amnt!(1.), KILOJOULE), Amnt!(1000.) * JOULE);
+    }
+
+    #[test]
+    fn test_rate_with_term_qty_without_dim() {
+        let price: Rate<Money, Weight> =
+            Rate::new(Amnt!(12.), EURO, Amnt!(1.), KILOGRAM);
+        let cost: Money = (Amnt!(500.) * GRAM) * price;
+        assert_eq!(cost, Amnt!(6.) * EURO);
+        assert_eq!(cost.unit(), EURO);
+        let weight: Weight = (Amnt!(3.) * EURO) / price;
+        assert_eq!(weight, Amnt!(250.) * GRAM);
+        let yield_: Rate<Weight, Money> = price.reciprocal();
+        let cost: Money = (Amnt!(2.) * KILOGRAM) / yield_;
+        assert_eq!(cost, Amnt!(2400.) * CENT);
+    }
+}
@@ -0,0 +1,774 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use crate::{Quantity, QuantityValue, Unit};
+
+/// Style used to represent the unit of a formatted quantity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnitStyle {
+    /// The symbol of the unit, like "km".
+    Symbol,
+    /// The name of the unit, pluralized unless the value is 1, like
+    /// "Kilometers".
+    Name,
+}
+
+/// Separator put between the value and the unit of a formatted quantity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnitSeparator {
+    /// A normal space (U+0020).
+    Space,
+    /// A thin space (U+2009).
+    ThinSpace,
+    /// A no-break space (U+00A0).
+    NoBreakSpace,
+    /// A narrow no-break space (U+202F).
+    NarrowNoBreakSpace,
+    /// No separator at all.
+    None,
+}
+
+impl UnitSeparator {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Space => " ",
+            Self::ThinSpace => "\u{2009}",
+            Self::NoBreakSpace => "\u{a0}",
+            Self::NarrowNoBreakSpace => "\u{202f}",
+            Self::None => "",
+        }
+    }
+}
+
+/// Notation used to represent the value of a formatted quantity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Notation {
+    /// Plain decimal notation, like "4200".
+    Plain,
+    /// Scientific notation with one digit before the decimal point, like
+    /// "4.2e3".
+    Scientific,
+    /// Engineering notation with an exponent being a multiple of 3, like
+    /// "42e-3".
+    Engineering,
+}
+
+/// Options for formatting quantities, extending the formatting done via
+/// `Display`.
+///
+/// Via `Display`, a quantity is formatted as its value followed by a space
+/// and the symbol of its unit. The precision given in the format spec is
+/// applied to the value, the alternate flag (`{:#}`) selects the name of the
+/// unit instead of its symbol.
+///
+/// An instance of `QuantityFormat` allows to select
+///
+/// * the unit style (symbol or name, see [UnitStyle]),
+/// * the separator between value and unit (see [UnitSeparator]),
+/// * the notation of the value (see [Notation]),
+/// * a precision (i.e. a fixed number of fractional digits) or a fixed
+///   number of significant digits, and
+/// * the character used as decimal separator.
+///
+/// When rounding is necessary, the value is rounded half away from zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::{Notation, QuantityFormat, UnitSeparator, UnitStyle};
+/// #[quantity]
+/// #[ref_unit(Meter, "m", NONE, prefixes = [MILLI, KILO])]
+/// struct Length {}
+///
+/// let len = Amnt!(1234.5) * METER;
+/// assert_eq!(format!("{len:#}"), "1234.5 Meters");
+/// let qf = QuantityFormat::new()
+///     .with_notation(Notation::Engineering)
+///     .with_significant_digits(3)
+///     .with_decimal_separator(',')
+///     .with_separator(UnitSeparator::NarrowNoBreakSpace);
+/// assert_eq!(qf.format(&len), "1,23e3\u{202f}m");
+/// let qf = QuantityFormat::new()
+///     .with_unit_style(UnitStyle::Name)
+///     .with_precision(2);
+/// assert_eq!(format!("{:>16}", qf.display(&len)), "  1234.50 Meters");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuantityFormat {
+    unit_style: UnitStyle,
+    separator: UnitSeparator,
+    notation: Notation,
+    precision: Option<usize>,
+    significant_digits: Option<usize>,
+    decimal_separator: char,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for QuantityFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl QuantityFormat {
+    /// Returns a new instance of `QuantityFormat` giving the same result as
+    /// `Display` (without format spec).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            unit_style: UnitStyle::Symbol,
+            separator: UnitSeparator::Space,
+            notation: Notation::Plain,
+            precision: None,
+            significant_digits: None,
+            decimal_separator: '.',
+        }
+    }
+
+    /// Returns `self` with the given unit style.
+    #[must_use]
+    pub const fn with_unit_style(self, unit_style: UnitStyle) -> Self {
+        Self { unit_style, ..self }
+    }
+
+    /// Returns `self` with the given separator between value and unit.
+    #[must_use]
+    pub const fn with_separator(self, separator: UnitSeparator) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Returns `self` with the given notation.
+    #[must_use]
+    pub const fn with_notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+
+    /// Returns `self` with the given number of fractional digits (of the
+    /// mantissa in case of scientific or engineering notation), replacing a
+    /// number of significant digits set before.
+    #[must_use]
+    pub const fn with_precision(self, precision: usize) -> Self {
+        Self {
+            precision: Some(precision),
+            significant_digits: None,
+            ..self
+        }
+    }
+
+    /// Returns `self` with the given number of significant digits, replacing
+    /// a precision set before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `significant_digits` is 0.
+    #[must_use]
+    pub const fn with_significant_digits(
+        self,
+        significant_digits: usize,
+    ) -> Self {
+        assert!(significant_digits > 0, "At least one digit needed.");
+        Self {
+            precision: None,
+            significant_digits: Some(significant_digits),
+            ..self
+        }
+    }
+
+    /// Returns `self` with the given decimal separator.
+    #[must_use]
+    pub const fn with_decimal_separator(
+        self,
+        decimal_separator: char,
+    ) -> Self {
+        Self {
+            decimal_separator,
+            ..self
+        }
+    }
+
+    /// Returns `qty` formatted according to `self`.
+    #[must_use]
+    pub fn format<Q: Quantity>(&self, qty: &Q) -> String {
+        self.format_with_precision(qty, self.precision)
+    }
+
+    /// Returns a wrapper of `qty` implementing `Display` by formatting `qty`
+    /// according to `self`.
+    ///
+    /// The width, fill and alignment given in the format spec are applied to
+    /// the result. The precision given in the format spec is applied unless
+    /// `self` defines a precision or a number of significant digits.
+    #[must_use]
+    pub const fn display<'a, Q: Quantity>(
+        &'a self,
+        qty: &'a Q,
+    ) -> FormattedQuantity<'a, Q> {
+        FormattedQuantity { format: self, qty }
+    }
+
+    fn format_with_precision<Q: Quantity>(
+        &self,
+        qty: &Q,
+        precision: Option<usize>,
+    ) -> String {
+        let value = self.format_value(&qty.value(), precision);
+        let unit = qty.unit();
+        if unit.symbol().is_empty() {
+            return value;
+        }
+        match self.unit_style {
+            UnitStyle::Symbol => {
+                format!("{}{}{}", value, self.separator.as_str(), unit)
+            }
+            UnitStyle::Name => {
+                let name = if value.trim_start_matches('-') == "1" {
+                    unit.name().to_owned()
+                } else {
+                    plural(unit.name())
+                };
+                format!("{}{}{}", value, self.separator.as_str(), name)
+            }
+        }
+    }
+
+    fn format_value<V: QuantityValue>(
+        &self,
+        value: &V,
+        precision: Option<usize>,
+    ) -> String {
+        let lit = value.to_string();
+        let Some(mut num) = DecimalNum::parse(&lit) else {
+            // not a finite number
+            return lit;
+        };
+        let sig = self.significant_digits;
+        let mut res = String::new();
+        match self.notation {
+            Notation::Plain => {
+                let n_frac = match (sig, precision) {
+                    (Some(sig), _) => {
+                        num.round_at(num.msd() - to_i32(sig) + 1);
+                        to_usize(to_i32(sig) - 1 - num.msd())
+                    }
+                    (None, Some(prec)) => {
+                        num.round_at(-to_i32(prec));
+                        prec
+                    }
+                    (None, None) => 0,
+                };
+                res.push_str(&num.to_plain(n_frac, self.decimal_separator));
+            }
+            Notation::Scientific | Notation::Engineering => {
+                if let Some(sig) = sig {
+                    num.round_at(num.msd() - to_i32(sig) + 1);
+                }
+                let mut exp = self.exponent(num.msd());
+                if let Some(prec) = precision.filter(|_| sig.is_none()) {
+                    num.round_at(exp - to_i32(prec));
+                    // rounding may have increased the magnitude
+                    exp = self.exponent(num.msd());
+                }
+                let n_frac = match (sig, precision) {
+                    (Some(sig), _) => {
+                        to_usize(to_i32(sig) - 1 - (num.msd() - exp))
+                    }
+                    (None, Some(prec)) => prec,
+                    (None, None) => 0,
+                };
+                num.exp -= exp;
+                res.push_str(&num.to_plain(n_frac, self.decimal_separator));
+                res.push_str(&format!("e{exp}"));
+            }
+        }
+        // a value rounded to zero is shown without sign
+        if num.negative && !num.digits.is_empty() {
+            res.insert(0, '-');
+        }
+        res
+    }
+
+    // Returns the exponent to be used for a number whose most significant
+    // digit is at position `msd`.
+    const fn exponent(&self, msd: i32) -> i32 {
+        match self.notation {
+            Notation::Engineering => msd.div_euclid(3) * 3,
+            _ => msd,
+        }
+    }
+}
+
+/// Wrapper of a quantity implementing `Display` by formatting the quantity
+/// according to a `QuantityFormat` (see [QuantityFormat::display]).
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct FormattedQuantity<'a, Q: Quantity> {
+    format: &'a QuantityFormat,
+    qty: &'a Q,
+}
+
+#[cfg(feature = "alloc")]
+impl<Q: Quantity> fmt::Display for FormattedQuantity<'_, Q> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = self.format.precision.or_else(|| form.precision());
+        let res = self.format.format_with_precision(self.qty, precision);
+        // `Formatter::pad` would truncate `res` to the precision, so the
+        // padding is done here
+        let n_chars = res.chars().count();
+        let n_fill =
+            form.width().map_or(0, |width| width.saturating_sub(n_chars));
+        if form.sign_aware_zero_pad() {
+            // like for numbers, the zeros are put between sign and digits
+            let digits = match res.strip_prefix('-') {
+                Some(digits) => {
+                    form.write_char('-')?;
+                    digits
+                }
+                None => &res,
+            };
+            for _ in 0..n_fill {
+                form.write_char('0')?;
+            }
+            return form.write_str(digits);
+        }
+        // like `Display`, right-aligned by default
+        let (n_pre, n_post) = match form.align() {
+            Some(fmt::Alignment::Left) => (0, n_fill),
+            Some(fmt::Alignment::Center) => {
+                (n_fill >> 1, n_fill - (n_fill >> 1))
+            }
+            _ => (n_fill, 0),
+        };
+        for _ in 0..n_pre {
+            form.write_char(form.fill())?;
+        }
+        form.write_str(&res)?;
+        for _ in 0..n_post {
+            form.write_char(form.fill())?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the plural of the unit name `name` to `out`, like "Meters per
+// Second" for "Meter per Second" or "Degrees Celsius" for "Degree Celsius".
+pub(crate) fn write_plural<W: Write>(out: &mut W, name: &str) -> fmt::Result {
+    let (head, tail) = match name.find(" per ") {
+        Some(idx) => name.split_at(idx),
+        None => (name, ""),
+    };
+    // the noun to be pluralized is the last word, except in names like
+    // "Degree Celsius"
+    let (pre, word, post) = match head.split_once(' ') {
+        Some(("Degree", _)) => {
+            let (word, post) = head.split_at("Degree".len());
+            ("", word, post)
+        }
+        _ => {
+            let idx = head.rfind(' ').map_or(0, |idx| idx + 1);
+            let (pre, word) = head.split_at(idx);
+            (pre, word, "")
+        }
+    };
+    let ends_with = |suffix: &str| {
+        word.len() >= suffix.len()
+            && word.as_bytes()[word.len() - suffix.len()..]
+                .eq_ignore_ascii_case(suffix.as_bytes())
+    };
+    out.write_str(pre)?;
+    if ["hertz", "lux", "siemens"].iter().any(|w| ends_with(w)) {
+        out.write_str(word)?;
+    } else if let Some(stem) = word.strip_suffix("oot") {
+        out.write_str(stem)?;
+        out.write_str("eet")?;
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|s| ends_with(s)) {
+        out.write_str(word)?;
+        out.write_str("es")?;
+    } else if ends_with("y")
+        && !ends_with("ay")
+        && !ends_with("ey")
+        && !ends_with("oy")
+    {
+        out.write_str(&word[..word.len() - 1])?;
+        out.write_str("ies")?;
+    } else {
+        out.write_str(word)?;
+        out.write_str("s")?;
+    }
+    out.write_str(post)?;
+    out.write_str(tail)
+}
+
+// Returns the plural of the unit name `name` (see `write_plural`).
+#[cfg(feature = "alloc")]
+pub(crate) fn plural(name: &str) -> String {
+    let mut res = String::with_capacity(name.len() + 3);
+    // writing to a `String` does not fail
+    let _ = write_plural(&mut res, name);
+    res
+}
+
+// Writer counting the chars written to it.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+// Writer checking whether the chars written to it are equal to a given
+// string.
+struct Matcher<'a>(Option<&'a str>);
+
+impl Matcher<'_> {
+    fn is_match(&self) -> bool {
+        self.0 == Some("")
+    }
+}
+
+impl Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0.and_then(|rest| rest.strip_prefix(s));
+        Ok(())
+    }
+}
+
+// Absolute value of a quantity, followed by a space and the symbol or - if
+// `alternate` is set - the name of its unit.
+struct QuantityBody<Q: Quantity> {
+    abs_amnt: Q::Value,
+    unit: Q::UnitType,
+    precision: Option<usize>,
+    alternate: bool,
+}
+
+impl<Q: Quantity> QuantityBody<Q> {
+    fn write_amnt<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self.precision {
+            Some(prec) => write!(out, "{:.*}", prec, self.abs_amnt),
+            None => write!(out, "{}", self.abs_amnt),
+        }
+    }
+}
+
+impl<Q: Quantity> fmt::Display for QuantityBody<Q> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_amnt(form)?;
+        form.write_char(' ')?;
+        if !self.alternate {
+            return form.write_str(self.unit.symbol());
+        }
+        let mut matcher = Matcher(Some("1"));
+        self.write_amnt(&mut matcher)?;
+        if matcher.is_match() {
+            form.write_str(self.unit.name())
+        } else {
+            write_plural(form, self.unit.name())
+        }
+    }
+}
+
+// Formats `qty` like `Formatter::pad_integral` would format the string
+// consisting of its absolute value and its unit, without allocating.
+pub(crate) fn fmt_quantity<Q: Quantity>(
+    qty: &Q,
+    form: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let zero = <Q::Value as QuantityValue>::ZERO;
+    // NaN is shown without sign, like by `f64`
+    let is_nan = qty.value() != qty.value();
+    let neg = qty.value() < zero;
+    let body = QuantityBody::<Q> {
+        abs_amnt: if neg { zero - qty.value() } else { qty.value() },
+        unit: qty.unit(),
+        precision: form.precision(),
+        alternate: form.alternate(),
+    };
+    let sign = match (neg, form.sign_plus()) {
+        _ if is_nan => None,
+        (true, _) => Some('-'),
+        (false, true) => Some('+'),
+        (false, false) => None,
+    };
+    let n_fill = match form.width() {
+        Some(width) => {
+            let mut counter = CharCounter(usize::from(sign.is_some()));
+            write!(counter, "{body}")?;
+            width.saturating_sub(counter.0)
+        }
+        None => 0,
+    };
+    if form.sign_aware_zero_pad() {
+        if let Some(sign) = sign {
+            form.write_char(sign)?;
+        }
+        for _ in 0..n_fill {
+            form.write_char('0')?;
+        }
+        return write!(form, "{body}");
+    }
+    let (n_pre, n_post) = match form.align() {
+        Some(fmt::Alignment::Left) => (0, n_fill),
+        Some(fmt::Alignment::Center) => (n_fill >> 1, n_fill - (n_fill >> 1)),
+        _ => (n_fill, 0),
+    };
+    let fill = form.fill();
+    for _ in 0..n_pre {
+        form.write_char(fill)?;
+    }
+    if let Some(sign) = sign {
+        form.write_char(sign)?;
+    }
+    write!(form, "{body}")?;
+    for _ in 0..n_post {
+        form.write_char(fill)?;
+    }
+    Ok(())
+}
+
+// Conversions between numbers of digits and positions of digits, which are
+// far from the limits of both types.
+#[cfg(feature = "alloc")]
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+const fn to_i32(n: usize) -> i32 {
+    n as i32
+}
+
+// Negative numbers are mapped to 0.
+#[cfg(feature = "alloc")]
+#[allow(clippy::cast_sign_loss)]
+const fn to_usize(n: i32) -> usize {
+    if n < 0 {
+        0
+    } else {
+        n as usize
+    }
+}
+
+// Decimal number given by its sign and the digits of its absolute value,
+// which is `digits` * 10^`exp` (`digits` interpreted as integer, without
+// leading or trailing zeros, empty for zero).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct DecimalNum {
+    negative: bool,
+    digits: Vec<u8>,
+    exp: i32,
+}
+
+#[cfg(feature = "alloc")]
+impl DecimalNum {
+    // Parses the string representation of a finite number as given by the
+    // implementations of `Display` for the types implementing
+    // `QuantityValue`.
+    fn parse(lit: &str) -> Option<Self> {
+        let (negative, lit) = match lit.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, lit.strip_prefix('+').unwrap_or(lit)),
+        };
+        let (mantissa, mut exp) = match lit.split_once(['e', 'E']) {
+            Some((mantissa, exp)) => (mantissa, exp.parse::<i32>().ok()?),
+            None => (lit, 0),
+        };
+        let (int_part, frac_part) =
+            mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return None;
+        }
+        let mut digits: Vec<u8> = Vec::with_capacity(mantissa.len());
+        for c in int_part.chars().chain(frac_part.chars()) {
+            digits.push(u8::try_from(c.to_digit(10)?).ok()?);
+        }
+        exp -= to_i32(frac_part.len());
+        let mut num = Self {
+            negative,
+            digits,
+            exp,
+        };
+        num.trim();
+        Some(num)
+    }
+
+    // Removes leading and trailing zeros.
+    fn trim(&mut self) {
+        let n_leading = self.digits.iter().take_while(|d| **d == 0).count();
+        self.digits.drain(..n_leading);
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+            self.exp += 1;
+        }
+        if self.digits.is_empty() {
+            self.exp = 0;
+        }
+    }
+
+    // Returns the position of the most significant digit, i.e. `n` so that
+    // 10^`n` <= |`self`| < 10^(`n` + 1), or 0 if `self` is zero.
+    const fn msd(&self) -> i32 {
+        if self.digits.is_empty() {
+            0
+        } else {
+            self.exp + to_i32(self.digits.len()) - 1
+        }
+    }
+
+    // Rounds `self` half away from zero to a multiple of 10^`pos`.
+    fn round_at(&mut self, pos: i32) {
+        if pos <= self.exp || self.digits.is_empty() {
+            return;
+        }
+        // number of digits left of position `pos`
+        let n_keep = to_i32(self.digits.len()) - (pos - self.exp);
+        let round_up =
+            n_keep >= 0 && self.digits[to_usize(n_keep)] >= 5;
+        self.digits.truncate(to_usize(n_keep));
+        self.exp = pos;
+        if round_up {
+            let mut idx = self.digits.len();
+            loop {
+                if idx == 0 {
+                    self.digits.insert(0, 1);
+                    break;
+                }
+                idx -= 1;
+                if self.digits[idx] == 9 {
+                    self.digits[idx] = 0;
+                } else {
+                    self.digits[idx] += 1;
+                    break;
+                }
+            }
+        }
+        self.trim();
+    }
+
+    // Returns the absolute value of `self` in plain decimal notation with at
+    // least `n_frac` fractional digits.
+    fn to_plain(&self, n_frac: usize, decimal_separator: char) -> String {
+        let digits: String =
+            self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let (int_part, mut frac_part) = if self.digits.is_empty() {
+            (String::from("0"), String::new())
+        } else if self.exp >= 0 {
+            (digits + &"0".repeat(to_usize(self.exp)), String::new())
+        } else {
+            let n_int = to_i32(self.digits.len()) + self.exp;
+            if n_int > 0 {
+                let (int_part, frac_part) = digits.split_at(to_usize(n_int));
+                (int_part.to_owned(), frac_part.to_owned())
+            } else {
+                (String::from("0"), "0".repeat(to_usize(-n_int)) + &digits)
+            }
+        };
+        if frac_part.len() < n_frac {
+            frac_part.push_str(&"0".repeat(n_frac - frac_part.len()));
+        }
+        if frac_part.is_empty() {
+            int_part
+        } else {
+            format!("{int_part}{decimal_separator}{frac_part}")
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Meter, "m", NONE)]
+    #[unit(Kilometer, "km", KILO, 1000)]
+    struct Length {}
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(plural("Meter"), "Meters");
+        assert_eq!(plural("Meter per Second"), "Meters per Second");
+        assert_eq!(plural("Square Kilometer"), "Square Kilometers");
+        assert_eq!(plural("Degree Celsius"), "Degrees Celsius");
+        assert_eq!(plural("Kilohertz"), "Kilohertz");
+        assert_eq!(plural("Foot"), "Feet");
+        assert_eq!(plural("Inch"), "Inches");
+        assert_eq!(plural("Henry"), "Henries");
+        assert_eq!(plural("Day"), "Days");
+    }
+
+    #[test]
+    fn test_decimal_num() {
+        let mut num = DecimalNum::parse("-0012.3400").unwrap();
+        assert_eq!(num.digits, [1, 2, 3, 4]);
+        assert_eq!(num.exp, -2);
+        assert_eq!(num.msd(), 1);
+        num.round_at(-1);
+        assert_eq!(num.to_plain(2, '.'), "12.30");
+        let mut num = DecimalNum::parse("9.96").unwrap();
+        num.round_at(-1);
+        assert_eq!(num.to_plain(0, ','), "10");
+        let mut num = DecimalNum::parse("0.0042").unwrap();
+        assert_eq!(num.msd(), -3);
+        assert_eq!(num.to_plain(5, ','), "0,00420");
+        num.round_at(-2);
+        assert_eq!(num.to_plain(0, '.'), "0");
+        assert!(DecimalNum::parse("NaN").is_none());
+        assert!(DecimalNum::parse("-inf").is_none());
+    }
+
+    #[test]
+    fn test_formatted_qty_padding() {
+        let qf = QuantityFormat::new().with_precision(1);
+        let len = 2.5_f64 * METER;
+        let neg = -2.5_f64 * METER;
+        assert_eq!(format!("{:10}", qf.display(&len)), "     2.5 m");
+        assert_eq!(format!("{:10.1}", len), "     2.5 m");
+        assert_eq!(format!("{:<10}", qf.display(&len)), "2.5 m     ");
+        assert_eq!(format!("{:>10}", qf.display(&len)), "     2.5 m");
+        assert_eq!(format!("{:*^10}", qf.display(&len)), "**2.5 m***");
+        assert_eq!(format!("{:010}", qf.display(&len)), "000002.5 m");
+        assert_eq!(format!("{:010}", qf.display(&neg)), "-00002.5 m");
+        // zero padding takes precedence over fill and alignment
+        assert_eq!(format!("{:*<010}", qf.display(&neg)), "-00002.5 m");
+        assert_eq!(format!("{:04}", qf.display(&neg)), "-2.5 m");
+    }
+
+    #[test]
+    fn test_display_not_a_number() {
+        let len = f64::NAN * METER;
+        assert_eq!(len.to_string(), "NaN m");
+        assert_eq!(format!("{:+}", len), "NaN m");
+        assert_eq!(format!("{:+}", -len), "NaN m");
+        assert_eq!(format!("{:>8}", len), "   NaN m");
+        assert_eq!(format!("{:#}", len), "NaN Meters");
+        let len = f64::INFINITY * KILOMETER;
+        assert_eq!(len.to_string(), "inf km");
+        assert_eq!((-len).to_string(), "-inf km");
+    }
+
+    #[test]
+    fn test_display_zero_pad() {
+        let len = 2.5_f64 * METER;
+        assert_eq!(format!("{:08.1}", len), "0002.5 m");
+        assert_eq!(format!("{:08.1}", -len), "-002.5 m");
+        assert_eq!(format!("{:+08.1}", len), "+002.5 m");
+        assert_eq!(format!("{:^08.1}", -len), "-002.5 m");
+    }
+}
@@ -9,8 +9,6 @@
 
 #![doc = include_str ! ("../README.md")]
 #![cfg_attr(not(feature = "std"), no_std)]
-// (unstable) features
-#![feature(const_trait_impl)]
 // activate some rustc lints
 #![deny(non_ascii_idents)]
 #![deny(unsafe_code)]
@@ -19,6 +17,8 @@
 #![warn(trivial_casts)]
 #![warn(unused)]
 #![allow(dead_code)]
+// lints removed from newer versions of clippy are kept below
+#![allow(renamed_and_removed_lints)]
 // activate some clippy lints
 #![warn(clippy::cast_possible_truncation)]
 #![warn(clippy::cast_possible_wrap)]
@@ -58,28 +58,61 @@
 #![warn(clippy::used_underscore_binding)]
 #![warn(clippy::wildcard_imports)]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
-use alloc::{format, string::String};
 use core::{
     cmp::Ordering,
     fmt,
-    ops::{Add, Div, Mul, Sub},
+    ops::{Add, Mul, Sub},
+    str::FromStr,
 };
 
-pub use value::{Amount, AMNT_ZERO, AMNT_ONE};
+pub use any_quantity::{AnyQuantity, DimensionError};
+pub use value::{Amount, QuantityValue, AMNT_ZERO, AMNT_ONE};
+#[cfg(feature = "fpdec")]
+pub use value::{Dec, Decimal};
 pub use converter::{ConversionTable, Converter};
+pub use formatting::{Notation, UnitSeparator, UnitStyle};
+#[cfg(feature = "alloc")]
+pub use formatting::{FormattedQuantity, QuantityFormat};
+pub use normalize::{Normalization, UnitSelection};
+pub use parse::ParseQuantityError;
+pub use point::PointQuantity;
+pub use power::{QtyPow, QtyRoot};
 pub use rate::Rate;
-pub use prefixes::SIPrefix;
+pub use unit_expr::{ParseUnitError, UnitExpr};
+#[cfg(feature = "alloc")]
+pub use registry::{RegisteredUnit, RegistryError, UnitRegistry};
+pub use prefixes::{BinaryPrefix, SIPrefix};
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
 
+mod any_quantity;
 pub mod converter;
+#[cfg(feature = "datathroughput")]
+pub mod datathroughput;
+#[cfg(feature = "datavolume")]
+pub mod datavolume;
+pub mod dimension;
+mod formatting;
+mod normalize;
+mod parse;
+mod point;
+mod power;
 pub mod prelude;
 mod rate;
 mod prefixes;
+#[cfg(feature = "alloc")]
+pub mod registry;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod si;
+mod unit_expr;
 
 #[doc(hidden)]
 pub mod value;
-mod si;
 
 
 
@@ -87,8 +120,8 @@ mod si;
 pub trait Unit:
     Copy + Eq + PartialEq + Sized + Mul<Amount> + fmt::Display
 {
-    /// Associated type of quantity
-    type QuantityType: Quantity<UnitType = Self>;
+    /// Associated type of quantity (using `Amount` as value type)
+    type QuantityType: Quantity<UnitType = Self, Value = Amount>;
 
     /// Returns an iterator over the variants of `Self`.
     fn iter<'a>() -> core::slice::Iter<'a, Self>;
@@ -114,6 +147,12 @@ pub trait Unit:
     /// Returns the SI prefix of `self`, or None is `self` is not a SI unit.
     fn si_prefix(&self) -> Option<SIPrefix>;
 
+    /// Returns the binary prefix of `self`, or None if `self` doesn't have a
+    /// binary prefix.
+    fn binary_prefix(&self) -> Option<BinaryPrefix> {
+        None
+    }
+
     /// Returns `1 * self`
     fn as_qty(&self) -> Self::QuantityType {
         Self::QuantityType::new(AMNT_ONE, *self)
@@ -121,12 +160,19 @@ pub trait Unit:
 
     /// Formats `self` using the given formatter.
     ///
+    /// The symbol of `self` is used, or - if the alternate flag is given -
+    /// its name.
+    ///
     /// # Errors
     ///
     /// This function will only return an instance of `Error` returned from the
     /// formatter.
     fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(self.symbol(), form)
+        if form.alternate() {
+            fmt::Display::fmt(self.name(), form)
+        } else {
+            fmt::Display::fmt(self.symbol(), form)
+        }
     }
 }
 
@@ -135,12 +181,12 @@ pub trait LinearScaledUnit: Unit {
     /// Unit used as reference for scaling the units.
     const REF_UNIT: Self;
 
-    /// Returns `Some(unit)` where `unit.scale()` == `Some(amnt)`, or `None` if
-    /// there is no such unit.
+    /// Returns `Some(unit)` where `unit.scale()` == `Some(amnt)` and
+    /// `unit.offset()` == 0, or `None` if there is no such unit.
     #[must_use]
     fn from_scale(amnt: Amount) -> Option<Self> {
         for unit in Self::iter() {
-            if unit.scale() == amnt {
+            if unit.scale() == amnt && unit.offset() == AMNT_ZERO {
                 return Some(*unit);
             }
         }
@@ -156,6 +202,16 @@ pub trait LinearScaledUnit: Unit {
     /// Returns `factor` so that `factor` * `Self::REFUNIT` == 1 * `self`.
     fn scale(&self) -> Amount;
 
+    /// Returns `offset` so that `x` * `self` ==
+    /// (`x` * `self.scale()` + `offset`) * `Self::REFUNIT`.
+    ///
+    /// The offset is 0 for all units except those defined with an offset,
+    /// like the degree Celsius in terms of Kelvin.
+    #[inline(always)]
+    fn offset(&self) -> Amount {
+        AMNT_ZERO
+    }
+
     /// Returns `factor` so that `factor` * `other` == 1 * `self`.
     #[inline(always)]
     fn ratio(&self, other: &Self) -> Amount {
@@ -163,145 +219,47 @@ pub trait LinearScaledUnit: Unit {
     }
 }
 
-#[const_trait]
 /// The abstract type of quantities.
-pub trait Quantity: Copy + Sized + Mul<Amount> {
-    /// Associated type of unit
-    type UnitType: Unit<QuantityType = Self>;
-
-    // FIXME
-    // /// Returns an iterator over the variants of `Self::UnitType`.
-    // fn iter_units<'a>() -> core::slice::Iter<'a, Self::UnitType> {
-    //     Self::UnitType::iter()
-    // }
-
-    // FIXME
-    // /// Returns `Some(unit)` where `unit.symbol()` == `symbol`, or `None` if
-    // /// there is no such unit.
-    // #[must_use]
-    // fn unit_from_symbol(symbol: &str) -> Option<Self::UnitType> {
-    //     for unit in Self::iter_units() {
-    //         if unit.symbol() == symbol {
-    //             return Some(*unit);
-    //         }
-    //     }
-    //     None
-    // }
+///
+/// Besides the access to their value and unit, all quantities provide the
+/// comparison operators and `Display`, and a default method for dividing
+/// quantities of the same type, so that generic code can be written once for
+/// any quantity. The default methods require both operands to have the same
+/// unit; quantities having a reference unit (see [HasRefUnit]) override them
+/// by converting the right operand to the unit of the left one.
+///
+/// Adding and subtracting quantities is provided by [AdditiveQuantity],
+/// because quantities whose values denote points on a scale (see
+/// [PointQuantity]) can't be added.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "length")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::length::*;
+///
+/// fn longest<Q: Quantity>(qties: &[Q]) -> Option<Q> {
+///     qties.iter().copied().reduce(|a, b| if b > a { b } else { a })
+/// }
+///
+/// fn ratio<Q: Quantity>(a: Q, b: Q) -> Q::Value {
+///     a.div_qty(b)
+/// }
+///
+/// let lengths = [Amnt!(2.) * METER, Amnt!(50.) * CENTIMETER];
+/// assert_eq!(longest(&lengths), Some(Amnt!(2.) * METER));
+/// assert_eq!(ratio(lengths[0], lengths[1]), Amnt!(4.));
+/// # }
+/// ```
+pub trait Quantity: Copy + Sized + PartialEq + PartialOrd + fmt::Display {
+    /// Associated type of the numerical part
+    type Value: QuantityValue;
 
-    /// Returns a new instance of the type implementing `Quantity`.
-    fn new(value: Amount, unit: Self::UnitType) -> Self;
-
-    /// Returns the amount of `self`.
-    fn value(&self) -> Amount;
-
-    /// Returns the unit of `self`.
-    fn unit(&self) -> Self::UnitType;
-
-    // FIXME
-    // /// Return `true` if `self` and `other` have the same unit and their amounts
-    // /// are equal, otherwise `false`.
-    // #[inline(always)]
-    // fn eq(&self, other: &Self) -> bool {
-    //     self.unit() == other.unit() && self.value() == other.value()
-    // }
-
-    // FIXME
-    // /// Returns the partial order of `self`s and `other`s amounts, if both have
-    // /// the same unit, otherwise `None`.
-    // fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-    //     if self.unit() == other.unit() {
-    //         PartialOrd::partial_cmp(&self.value(), &other.value())
-    //     } else {
-    //         None
-    //     }
-    // }
-
-    // FIXME
-    // /// Returns the sum of `self` and `other`, if both have the same unit.
-    // ///
-    // /// # Panics
-    // ///
-    // /// Panics if `self` and `other` have different units.
-    // fn add(self, rhs: Self) -> Self {
-    //     if self.unit() == rhs.unit() {
-    //         return Self::new(self.value() + rhs.value(), self.unit());
-    //     }
-    //     panic!(
-    //         "Can't add '{}' and '{}'.",
-    //         self.unit().symbol(),
-    //         rhs.unit().symbol()
-    //     );
-    // }
-
-    // FIXME
-    // /// Returns the difference between `self` and `other`, if both have the same
-    // /// unit.
-    // ///
-    // /// # Panics
-    // ///
-    // /// Panics if `self` and `other` have different units.
-    // fn sub(self, rhs: Self) -> Self {
-    //     if self.unit() == rhs.unit() {
-    //         return Self::new(self.value() - rhs.value(), self.unit());
-    //     }
-    //     panic!(
-    //         "Can't subtract '{}' and '{}'.",
-    //         self.unit().symbol(),
-    //         rhs.unit().symbol(),
-    //     );
-    // }
-
-    // FIXME
-    // /// Returns the quotient `self` / `other`, if both have the same unit.
-    // ///
-    // /// # Panics
-    // ///
-    // /// Panics if `self` and `other` have different units.
-    // fn div(self, rhs: Self) -> Amount {
-    //     if self.unit() == rhs.unit() {
-    //         return self.value() / rhs.value();
-    //     }
-    //     panic!(
-    //         "Can't divide '{}' and '{}'.",
-    //         self.unit().symbol(),
-    //         rhs.unit().symbol()
-    //     );
-    // }
-
-    // FIXME
-    // /// Formats `self` using the given formatter.
-    // ///
-    // /// # Errors
-    // ///
-    // /// This function will only return an instance of `Error` returned from the
-    // /// formatter.
-    // fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-    //     match self.unit().symbol() {
-    //         "" => fmt::Display::fmt(&self.value(), form),
-    //         _ => {
-    //             let tmp: String;
-    //             let amnt_non_neg = self.value() >= AMNT_ZERO;
-    //             #[cfg(feature = "fpdec")]
-    //             let abs_amnt = self.amount().abs();
-    //             #[cfg(not(feature = "fpdec"))]
-    //             let abs_amnt = if amnt_non_neg {
-    //                 self.value()
-    //             } else {
-    //                 -self.value()
-    //             };
-    //             if let Some(prec) = form.precision() {
-    //                 tmp = format!("{:.*} {}", prec, abs_amnt, self.unit());
-    //             } else {
-    //                 tmp = format!("{} {}", abs_amnt, self.unit());
-    //             }
-    //             form.pad_integral(amnt_non_neg, "", &tmp)
-    //         }
-    //     }
-    // }
-}
+    /// Associated type of unit
+    type UnitType: Unit;
 
-/// TODO: add some good documentation
-pub trait QuantityImpl: Quantity {
     /// Returns an iterator over the variants of `Self::UnitType`.
     fn iter_units<'a>() -> core::slice::Iter<'a, Self::UnitType> {
         Self::UnitType::iter()
@@ -319,6 +277,15 @@ pub trait QuantityImpl: Quantity {
         None
     }
 
+    /// Returns a new instance of the type implementing `Quantity`.
+    fn new(value: Self::Value, unit: Self::UnitType) -> Self;
+
+    /// Returns the amount of `self`.
+    fn value(&self) -> Self::Value;
+
+    /// Returns the unit of `self`.
+    fn unit(&self) -> Self::UnitType;
+
     /// Return `true` if `self` and `other` have the same unit and their amounts
     /// are equal, otherwise `false`.
     #[inline(always)]
@@ -336,12 +303,90 @@ pub trait QuantityImpl: Quantity {
         }
     }
 
+    /// Returns the quotient `self` / `other`, if both have the same unit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` have different units.
+    fn div_qty(self, rhs: Self) -> Self::Value {
+        if self.unit() == rhs.unit() {
+            return self.value() / rhs.value();
+        }
+        panic!(
+            "Can't divide '{}' and '{}'.",
+            self.unit().symbol(),
+            rhs.unit().symbol()
+        );
+    }
+
+    /// Formats `self` using the given formatter.
+    ///
+    /// The value of `self` is followed by a space and the symbol of its unit,
+    /// or - if the alternate flag is given - by the name of its unit (in
+    /// plural unless the value is 1). See [QuantityFormat] for more options.
+    ///
+    /// # Errors
+    ///
+    /// This function will only return an instance of `Error` returned from the
+    /// formatter.
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unit().symbol() {
+            "" => fmt::Display::fmt(&self.value(), form),
+            _ => formatting::fmt_quantity(self, form),
+        }
+    }
+}
+
+/// Trait for quantities which can be added to and subtracted from each other,
+/// i.e. all quantities except those whose values denote points on a scale
+/// (see [PointQuantity]).
+///
+/// Like those of [Quantity], the default methods require both operands to
+/// have the same unit; quantities having a reference unit override them by
+/// converting the right operand to the unit of the left one. The operators
+/// `+` and `-` are available via the supertraits [Add] and [Sub].
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "length")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::length::*;
+///
+/// fn total<Q: AdditiveQuantity>(first: Q, others: &[Q]) -> Q {
+///     others.iter().fold(first, |acc, qty| acc + *qty)
+/// }
+///
+/// let lengths = [Amnt!(2.) * METER, Amnt!(30.) * CENTIMETER];
+/// let sum = total(Amnt!(1.) * KILOMETER, &lengths);
+/// assert_eq!(sum.unit(), KILOMETER);
+/// assert_eq!(sum, Amnt!(1002.3) * METER);
+/// # }
+/// ```
+///
+/// Points are not additive, so the following fails to compile:
+///
+/// ```compile_fail
+/// use quantities::prelude::*;
+/// use quantities::si::temperature::*;
+///
+/// fn total<Q: AdditiveQuantity>(first: Q, others: &[Q]) -> Q {
+///     others.iter().fold(first, |acc, qty| acc + *qty)
+/// }
+///
+/// let temps = [Amnt!(20.) * DEGREE_CELSIUS, Amnt!(5.) * DEGREE_CELSIUS];
+/// let sum = total(temps[0], &temps[1..]);
+/// ```
+pub trait AdditiveQuantity:
+    Quantity + Add<Output = Self> + Sub<Output = Self>
+{
     /// Returns the sum of `self` and `other`, if both have the same unit.
     ///
     /// # Panics
     ///
     /// Panics if `self` and `other` have different units.
-    fn add(self, rhs: Self) -> Self {
+    fn add_qty(self, rhs: Self) -> Self {
         if self.unit() == rhs.unit() {
             return Self::new(self.value() + rhs.value(), self.unit());
         }
@@ -358,7 +403,7 @@ pub trait QuantityImpl: Quantity {
     /// # Panics
     ///
     /// Panics if `self` and `other` have different units.
-    fn sub(self, rhs: Self) -> Self {
+    fn sub_qty(self, rhs: Self) -> Self {
         if self.unit() == rhs.unit() {
             return Self::new(self.value() - rhs.value(), self.unit());
         }
@@ -369,14 +414,14 @@ pub trait QuantityImpl: Quantity {
         );
     }
 
-    /// Returns the quotient `self` / `other`, if both have the same unit.
+    /// Returns the remainder of `self` / `other`, if both have the same unit.
     ///
     /// # Panics
     ///
     /// Panics if `self` and `other` have different units.
-    fn div(self, rhs: Self) -> Amount {
+    fn rem_qty(self, rhs: Self) -> Self {
         if self.unit() == rhs.unit() {
-            return self.value() / rhs.value();
+            return Self::new(self.value() % rhs.value(), self.unit());
         }
         panic!(
             "Can't divide '{}' and '{}'.",
@@ -384,65 +429,118 @@ pub trait QuantityImpl: Quantity {
             rhs.unit().symbol()
         );
     }
+}
 
-    /// Formats `self` using the given formatter.
+/// Extension of [Quantity], providing parsing as well as powers and roots.
+pub trait QuantityImpl: Quantity {
+    /// Returns an instance of `Self` parsed from `lit`, which must consist of
+    /// a number followed by the symbol of a unit of `Self::UnitType`,
+    /// optionally separated by whitespace (for example "17.4 kg" or "3.5km").
+    ///
+    /// The letter 'u' is accepted as alias for the prefix 'µ' (micro).
     ///
     /// # Errors
     ///
-    /// This function will only return an instance of `Error` returned from the
-    /// formatter.
-    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.unit().symbol() {
-            "" => fmt::Display::fmt(&self.value(), form),
-            _ => {
-                let tmp: String;
-                let amnt_non_neg = self.value() >= AMNT_ZERO;
-                #[cfg(feature = "fpdec")]
-                let abs_amnt = self.amount().abs();
-                #[cfg(not(feature = "fpdec"))]
-                let abs_amnt = if amnt_non_neg {
-                    self.value()
-                } else {
-                    -self.value()
-                };
-                if let Some(prec) = form.precision() {
-                    tmp = format!("{:.*} {}", prec, abs_amnt, self.unit());
-                } else {
-                    tmp = format!("{} {}", abs_amnt, self.unit());
-                }
-                form.pad_integral(amnt_non_neg, "", &tmp)
-            }
-        }
+    /// Returns `ParseQuantityError::EmptyInput` if `lit` is empty or contains
+    /// only whitespace, `ParseQuantityError::InvalidNumber` if the numerical
+    /// part can't be parsed as `Self::Value` and
+    /// `ParseQuantityError::UnknownUnit` if there is no unit with the given
+    /// symbol.
+    fn parse(lit: &str) -> Result<Self, ParseQuantityError>
+    where
+        Self::Value: FromStr,
+    {
+        let (num, sym) = parse::split_qty_lit(lit)?;
+        let value = num
+            .parse::<Self::Value>()
+            .map_err(|_| ParseQuantityError::InvalidNumber)?;
+        let unit = Self::iter_units()
+            .find(|unit| parse::symbol_matches(unit.symbol(), sym))
+            .ok_or(ParseQuantityError::UnknownUnit)?;
+        Ok(Self::new(value, *unit))
+    }
+
+    /// Returns `self` raised to the power `N` (see [QtyPow]).
+    #[must_use]
+    fn powi<const N: i32>(self) -> <Self as QtyPow<N>>::Output
+    where
+        Self: QtyPow<N>,
+    {
+        QtyPow::<N>::pow(self)
     }
+
+    /// Returns the square root of `self` (see [QtyRoot]).
+    #[must_use]
+    fn sqrt(self) -> <Self as QtyRoot<2>>::Output
+    where
+        Self: QtyRoot<2>,
+    {
+        QtyRoot::<2>::root(self)
+    }
+
+    /// Returns the cube root of `self` (see [QtyRoot]).
+    #[must_use]
+    fn cbrt(self) -> <Self as QtyRoot<3>>::Output
+    where
+        Self: QtyRoot<3>,
+    {
+        QtyRoot::<3>::root(self)
+    }
+
 }
 
 /// Trait for quantities having a reference unit
-pub trait HasRefUnit: QuantityImpl + Add<Self> + Sub<Self> + Div<Self>
+pub trait HasRefUnit: QuantityImpl
 where
     <Self as Quantity>::UnitType: LinearScaledUnit,
 {
     /// Unit used as reference for scaling the units of `Self::UnitType`.
     const REF_UNIT: <Self as Quantity>::UnitType;
 
-    /// Returns `Some(unit)` where `unit.scale()` == `amnt`, or `None` if
-    /// there is no such unit.
+    /// Returns `Some(unit)` where `unit.scale()` == `amnt` and
+    /// `unit.offset()` == 0, or `None` if there is no such unit.
     #[must_use]
     fn unit_from_scale(amnt: Amount) -> Option<Self::UnitType> {
         for unit in Self::iter_units() {
-            if unit.scale() == amnt {
+            if unit.scale() == amnt && unit.offset() == AMNT_ZERO {
                 return Some(*unit);
             }
         }
         None
     }
 
-    /// Returns `factor` so that `factor` * `unit` == `self`.
+    /// Returns the unit of `Self` given by the unit expression `expr` (see
+    /// [UnitExpr]), like "m·s⁻¹" for the unit "m/s" of a velocity.
+    ///
+    /// The unit symbols in `expr` are looked up in the units of `Self` and -
+    /// if not found there - in the units of the enabled SI quantities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseUnitError::InvalidSyntax` if `expr` is malformed,
+    /// `ParseUnitError::DimensionMismatch` if the dimension of `expr` differs
+    /// from the dimension of `Self` and `ParseUnitError::UnknownUnit` if
+    /// `expr` contains an unknown unit symbol or there is no unit of `Self`
+    /// with the scale given by `expr`.
+    fn unit_from_expr(expr: &str) -> Result<Self::UnitType, ParseUnitError>
+    where
+        Self: dimension::HasDimension,
+    {
+        unit_expr::unit_from_expr::<Self>(expr)
+    }
+
+    /// Returns `amount` so that `amount` * `unit` == `self`.
     #[inline(always)]
-    fn equiv_amount(&self, unit: Self::UnitType) -> Amount {
+    fn equiv_amount(&self, unit: Self::UnitType) -> Self::Value {
         if self.unit() == unit {
-            self.value()
+            return self.value();
+        }
+        let amount = self.value().mul_amount(self.unit().ratio(&unit));
+        let offset = self.unit().offset() - unit.offset();
+        if offset == AMNT_ZERO {
+            amount
         } else {
-            self.unit().ratio(&unit) * self.value()
+            amount + Self::Value::from_amount(offset / unit.scale())
         }
     }
 
@@ -485,30 +583,63 @@ where
 
     /// Returns the quotient `self` / `other`
     #[inline]
-    fn div(self, rhs: Self) -> Amount {
+    fn div(self, rhs: Self) -> Self::Value {
         self.value() / rhs.equiv_amount(self.unit())
     }
 
+    /// Returns the remainder of `self` / `other` in `self`s unit
+    #[inline]
+    fn rem(self, rhs: Self) -> Self {
+        Self::new(self.value() % rhs.equiv_amount(self.unit()), self.unit())
+    }
+
+    /// Returns the sum of the quantities yielded by `iter`, converted to
+    /// `unit`, i.e. zero in `unit` if `iter` is empty.
+    ///
+    /// In contrast, the impl of `Sum` generated by macro `quantity` takes
+    /// the unit of the first item and falls back to `Self::REF_UNIT` for an
+    /// empty iterator.
+    fn sum_in<I>(iter: I, unit: Self::UnitType) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        iter.into_iter()
+            .fold(Self::new(Self::Value::ZERO, unit), HasRefUnit::add)
+    }
+
+    /// Returns `qty` where `qty` == `self` and `qty.unit()` is the unit
+    /// best fitting the amount of `self`, i.e. the unit giving an absolute
+    /// value in the range [1, 1000) - taking only SI units into account if
+    /// `Self::REF_UNIT` is a SI unit.
+    ///
+    /// For example, 0.0042 m is normalized to 4.2 mm. See [Normalization]
+    /// for details and [HasRefUnit::to_best_unit] for other options.
+    #[must_use]
+    fn normalize(&self) -> Self {
+        self.to_best_unit(&Normalization::new())
+    }
+
+    /// Returns `qty` where `qty` == `self` and `qty.unit()` is the unit
+    /// best fitting the amount of `self` according to the options given in
+    /// `normalization`, or `self` if no unit is selected by these options.
+    #[must_use]
+    fn to_best_unit(
+        &self,
+        normalization: &Normalization<'_, Self::UnitType>,
+    ) -> Self {
+        match normalization.best_unit(self) {
+            Some(unit) => self.convert(unit),
+            None => *self,
+        }
+    }
+
     #[doc(hidden)]
     /// Returns a new instance of the type implementing `HasRefUnit`, equivalent
-    /// to `amount * Self::REF_UNIT`, converted to the unit with the greatest
-    /// scale less than or equal to `amount` or - if there is no such unit - to
-    /// the unit with the smallest scale greater than `amount`, in any case
-    /// taking only SI units into account if Self::REF_UNIT is a SI unit.
+    /// to `amount * Self::REF_UNIT`, converted to the best-fitting unit (see
+    /// [HasRefUnit::normalize]).
     #[must_use]
-    fn _fit(amount: Amount) -> Self {
-        let take_all = Self::REF_UNIT.si_prefix().is_none();
-        let mut it =
-            Self::iter_units().filter(|u| take_all || u.si_prefix().is_some());
-        // `it` returns atleast the reference unit, so its safe to unwrap here
-        let first = it.next().unwrap();
-        let last = it
-            .filter(|u| u.scale() > first.scale() && u.scale() <= amount)
-            .last();
-        match last {
-            Some(unit) => Self::new(amount / unit.scale(), *unit),
-            None => Self::new(amount / first.scale(), *first),
-        }
+    fn _fit(amount: Self::Value) -> Self {
+        Self::new(amount, Self::REF_UNIT).normalize()
     }
 }
 
@@ -573,15 +704,16 @@ impl Mul<Amount> for One {
 }
 
 impl Quantity for Amount {
+    type Value = Self;
     type UnitType = One;
 
     #[inline(always)]
-    fn new(amount: Amount, _unit: Self::UnitType) -> Self {
+    fn new(amount: Self, _unit: Self::UnitType) -> Self {
         amount
     }
 
     #[inline(always)]
-    fn value(&self) -> Amount {
+    fn value(&self) -> Self {
         *self
     }
 
@@ -591,13 +723,15 @@ impl Quantity for Amount {
     }
 }
 
+impl AdditiveQuantity for Amount {}
+
 impl QuantityImpl for Amount {}
 
 impl HasRefUnit for Amount {
     const REF_UNIT: One = ONE;
 
     #[inline(always)]
-    fn _fit(amount: Amount) -> Self {
+    fn _fit(amount: Self) -> Self {
         amount
     }
 }
@@ -0,0 +1,318 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use crate::{
+    Amnt, Amount, HasRefUnit, LinearScaledUnit, QuantityValue, AMNT_ONE,
+    AMNT_ZERO,
+};
+
+/// Policy selecting the units taken into account when normalizing a
+/// quantity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitSelection<'a, U: LinearScaledUnit> {
+    /// Only SI units, if the reference unit is a SI unit, otherwise all
+    /// units.
+    Auto,
+    /// All units.
+    All,
+    /// Only SI units, i.e. units having a SI prefix (incl. `NONE`).
+    SIPrefixed,
+    /// Only SI units having a SI prefix whose exponent is a multiple of 3
+    /// (like `MILLI` or `KILO`, but not `CENTI` or `HECTO`).
+    Engineering,
+    /// Only the given units.
+    Units(&'a [U]),
+}
+
+/// Options for selecting the best-fitting unit of a quantity (see
+/// [HasRefUnit::to_best_unit]).
+///
+/// The best-fitting unit is the unit giving an absolute value within the
+/// range [`lower`, `upper`), choosing the unit with the greatest scale if
+/// more than one unit does. If there is no such unit, the unit giving the
+/// value nearest to that range is chosen (comparing the ratios value / upper
+/// and lower / value). In any case, only units selected by the given
+/// [UnitSelection] and not having an offset are taken into account.
+///
+/// By default, the range is [1, 1000) and the units are selected by
+/// `UnitSelection::Auto`.
+///
+/// # Example
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::{Normalization, UnitSelection};
+/// #[quantity]
+/// #[ref_unit(Meter, "m", NONE, prefixes = [MILLI..=DECI, KILO])]
+/// #[unit(Foot, "ft", 0.3048)]
+/// struct Length {}
+///
+/// let len = Amnt!(0.0042) * METER;
+/// assert_eq!(len.normalize().unit(), MILLIMETER);
+/// let norm = Normalization::new().with_selection(UnitSelection::Engineering);
+/// let len = Amnt!(0.042) * METER;
+/// assert_eq!(len.to_best_unit(&norm).unit(), MILLIMETER);
+/// let norm = Normalization::new()
+///     .with_selection(UnitSelection::Units(&[METER, FOOT]))
+///     .with_range(Amnt!(1.), Amnt!(10.));
+/// let len = Amnt!(2.) * METER;
+/// assert_eq!(len.to_best_unit(&norm).unit(), METER);
+/// let len = Amnt!(0.6) * METER;
+/// assert_eq!(len.to_best_unit(&norm).unit(), FOOT);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normalization<'a, U: LinearScaledUnit> {
+    selection: UnitSelection<'a, U>,
+    lower: Amount,
+    upper: Amount,
+}
+
+impl<U: LinearScaledUnit> Default for Normalization<'_, U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, U: LinearScaledUnit> Normalization<'a, U> {
+    /// Returns a new instance of `Normalization` with the default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            selection: UnitSelection::Auto,
+            lower: AMNT_ONE,
+            upper: Amnt!(1000),
+        }
+    }
+
+    /// Returns `self` with the units selected by `selection`.
+    #[must_use]
+    pub const fn with_selection(
+        self,
+        selection: UnitSelection<'a, U>,
+    ) -> Self {
+        Self { selection, ..self }
+    }
+
+    /// Returns `self` with the range [`lower`, `upper`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lower` is not greater than zero or `upper` is not greater
+    /// than `lower`.
+    #[must_use]
+    pub fn with_range(self, lower: Amount, upper: Amount) -> Self {
+        assert!(
+            AMNT_ZERO < lower && lower < upper,
+            "Invalid range for normalization."
+        );
+        Self {
+            lower,
+            upper,
+            ..self
+        }
+    }
+
+    /// Returns the units selected by `self` in ascending order of their
+    /// scales.
+    fn candidates<Q>(&self) -> impl Iterator<Item = U> + '_
+    where
+        Q: HasRefUnit<UnitType = U>,
+    {
+        let selection = match self.selection {
+            UnitSelection::Auto if Q::REF_UNIT.si_prefix().is_some() => {
+                UnitSelection::SIPrefixed
+            }
+            UnitSelection::Auto => UnitSelection::All,
+            selection => selection,
+        };
+        Q::iter_units().copied().filter(move |unit| {
+            unit.offset() == AMNT_ZERO
+                && match selection {
+                    UnitSelection::Auto | UnitSelection::All => true,
+                    UnitSelection::SIPrefixed => unit.si_prefix().is_some(),
+                    UnitSelection::Engineering => unit
+                        .si_prefix()
+                        .is_some_and(|prefix| prefix.exp() % 3 == 0),
+                    UnitSelection::Units(units) => units.contains(unit),
+                }
+        })
+    }
+
+    /// Returns the best-fitting unit for `qty`, or `None` if no unit is
+    /// selected by `self`.
+    ///
+    /// A zero quantity keeps its unit, if selected, otherwise it gets the
+    /// reference unit, if selected, or the selected unit with the smallest
+    /// scale.
+    #[must_use]
+    pub fn best_unit<Q>(&self, qty: &Q) -> Option<U>
+    where
+        Q: HasRefUnit<UnitType = U>,
+    {
+        let zero = <Q::Value as QuantityValue>::ZERO;
+        let value = qty.equiv_amount(Q::REF_UNIT);
+        if value == zero {
+            return self
+                .candidates::<Q>()
+                .find(|unit| *unit == qty.unit())
+                .or_else(|| {
+                    self.candidates::<Q>().find(|unit| *unit == Q::REF_UNIT)
+                })
+                .or_else(|| self.candidates::<Q>().next());
+        }
+        let abs_value = if value < zero { zero - value } else { value };
+        let lower = Q::Value::from_amount(self.lower);
+        let upper = Q::Value::from_amount(self.upper);
+        // the units are sorted by ascending scales, so the values are
+        // descending
+        let mut above: Option<(U, Q::Value)> = None;
+        let mut within: Option<U> = None;
+        let mut below: Option<(U, Q::Value)> = None;
+        for unit in self.candidates::<Q>() {
+            let mantissa = abs_value.div_amount(unit.scale());
+            if mantissa >= upper {
+                above = Some((unit, mantissa));
+            } else if mantissa >= lower {
+                within = Some(unit);
+            } else {
+                below = Some((unit, mantissa));
+                break;
+            }
+        }
+        match (within, above, below) {
+            (Some(unit), ..) => Some(unit),
+            // choose the unit giving the value nearest to the range, i.e.
+            // the one with the smaller ratio of mantissa / upper and
+            // lower / mantissa
+            (None, Some((unit_a, m_a)), Some((unit_b, m_b))) => {
+                if m_a * m_b < lower * upper {
+                    Some(unit_a)
+                } else {
+                    Some(unit_b)
+                }
+            }
+            (None, Some((unit, _)), None) | (None, None, Some((unit, _))) => {
+                Some(unit)
+            }
+            (None, None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Meter, "m", NONE, prefixes = [MILLI..=DECI, KILO])]
+    #[unit(Foot, "ft", 0.3048)]
+    struct Length {}
+
+    #[quantity]
+    #[ref_unit(Second, "s", NONE, prefixes = [MILLI])]
+    #[unit(Minute, "min", 60)]
+    #[unit(Hour, "h", 3600)]
+    struct Time {}
+
+    #[quantity]
+    #[ref_unit(Point, "pt")]
+    #[unit(Hundred_Points, "hpt", 100)]
+    #[unit(Shifted_Kilo_Point, "skpt", 1000, 5)]
+    struct Level {}
+
+    #[test]
+    fn test_normalize() {
+        let len = (Amnt!(0.0042) * METER).normalize();
+        assert_eq!(len.unit(), MILLIMETER);
+        assert_eq!(len.value(), Amnt!(4.2));
+        assert_eq!((Amnt!(-2500.) * METER).normalize().unit(), KILOMETER);
+        let len = Amnt!(0.05) * METER;
+        assert_eq!(len.normalize().unit(), CENTIMETER);
+        let norm =
+            Normalization::new().with_selection(UnitSelection::Engineering);
+        assert_eq!(len.to_best_unit(&norm).unit(), MILLIMETER);
+        // only SI units are taken into account
+        assert_eq!((Amnt!(2.) * FOOT).normalize().unit(), DECIMETER);
+        let t = Amnt!(5400.) * SECOND;
+        assert_eq!(t.normalize().unit(), SECOND);
+    }
+
+    #[test]
+    fn test_selection() {
+        let t = Amnt!(5400.) * SECOND;
+        let norm = Normalization::new()
+            .with_selection(UnitSelection::All)
+            .with_range(Amnt!(1.), Amnt!(60.));
+        assert_eq!(t.to_best_unit(&norm).unit(), HOUR);
+        let norm = Normalization::new()
+            .with_selection(UnitSelection::Units(&[SECOND, MINUTE]))
+            .with_range(Amnt!(1.), Amnt!(60.));
+        assert_eq!(t.to_best_unit(&norm).unit(), MINUTE);
+        // the reference unit is not a SI unit, so all units are selected
+        let lvl = Amnt!(500.) * POINT;
+        assert_eq!(lvl.normalize().unit(), HUNDRED_POINTS);
+    }
+
+    #[test]
+    fn test_nothing_within_range() {
+        let norm = Normalization::new()
+            .with_selection(UnitSelection::Units(&[SECOND, HOUR]))
+            .with_range(Amnt!(1.), Amnt!(60.));
+        // 120 s is nearer to the range than 0.0333 h
+        let t = Amnt!(120.) * SECOND;
+        assert_eq!(t.to_best_unit(&norm).unit(), SECOND);
+        // 0.25 h is nearer to the range than 900 s
+        let t = Amnt!(900.) * SECOND;
+        assert_eq!(t.to_best_unit(&norm).unit(), HOUR);
+        // all values are above or below the range
+        let t = Amnt!(7200000.) * SECOND;
+        assert_eq!(t.to_best_unit(&norm).unit(), HOUR);
+        let t = Amnt!(0.5) * HOUR;
+        let norm = norm.with_range(Amnt!(2000.), Amnt!(3000.));
+        assert_eq!(t.to_best_unit(&norm).unit(), SECOND);
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!((Amnt!(0.0) * KILOMETER).normalize().unit(), KILOMETER);
+        // unit not selected => reference unit
+        assert_eq!((Amnt!(0.0) * FOOT).normalize().unit(), METER);
+        // neither unit nor reference unit selected => smallest scale
+        let norm = Normalization::new()
+            .with_selection(UnitSelection::Units(&[KILOMETER, MILLIMETER]));
+        let len = (Amnt!(0.0) * FOOT).to_best_unit(&norm);
+        assert_eq!(len.unit(), MILLIMETER);
+    }
+
+    #[test]
+    fn test_no_unit_selected() {
+        let norm =
+            Normalization::new().with_selection(UnitSelection::Units(&[]));
+        let t = Amnt!(5400.) * SECOND;
+        assert!(norm.best_unit(&t).is_none());
+        assert_eq!(t.to_best_unit(&norm).unit(), SECOND);
+    }
+
+    #[test]
+    fn test_units_with_offset_ignored() {
+        let lvl = Amnt!(5000.) * POINT;
+        assert_eq!(lvl.normalize().unit(), HUNDRED_POINTS);
+        let norm = Normalization::new()
+            .with_selection(UnitSelection::Units(&[SHIFTED_KILO_POINT]));
+        assert!(norm.best_unit(&lvl).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range for normalization.")]
+    fn test_invalid_range() {
+        let _ = Normalization::<TimeUnit>::new()
+            .with_range(Amnt!(10.), Amnt!(10.));
+    }
+}
@@ -0,0 +1,174 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::fmt;
+
+/// An error which can be returned when parsing a quantity from a string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseQuantityError {
+    /// The given string is empty or consists only of whitespace.
+    EmptyInput,
+    /// The numerical part of the given string can't be parsed.
+    InvalidNumber,
+    /// The unit symbol in the given string does not denote a unit of the
+    /// quantity.
+    UnknownUnit,
+}
+
+impl fmt::Display for ParseQuantityError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::EmptyInput => "cannot parse quantity from empty string",
+            Self::InvalidNumber => "invalid number in quantity literal",
+            Self::UnknownUnit => "unknown unit in quantity literal",
+        };
+        fmt::Display::fmt(msg, form)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseQuantityError {}
+
+/// Splits `lit` into its numerical part and its unit symbol, both without
+/// surrounding whitespace.
+pub(crate) fn split_qty_lit(
+    lit: &str,
+) -> Result<(&str, &str), ParseQuantityError> {
+    let lit = lit.trim();
+    if lit.is_empty() {
+        return Err(ParseQuantityError::EmptyInput);
+    }
+    let bytes = lit.as_bytes();
+    let is_digit = |idx: usize| bytes.get(idx).is_some_and(u8::is_ascii_digit);
+    let mut end = 0;
+    if matches!(bytes[0], b'+' | b'-') {
+        end = 1;
+    }
+    while end < bytes.len() {
+        match bytes[end] {
+            b'0'..=b'9' | b'.' => end += 1,
+            // an exponent must be followed by (signed) digits, otherwise the
+            // char starts the unit symbol
+            b'e' | b'E' if is_digit(end + 1) => end += 2,
+            b'e' | b'E'
+                if matches!(bytes.get(end + 1), Some(b'+' | b'-'))
+                    && is_digit(end + 2) =>
+            {
+                end += 3;
+            }
+            _ => break,
+        }
+    }
+    if end == 0 {
+        return Err(ParseQuantityError::InvalidNumber);
+    }
+    Ok((&lit[..end], lit[end..].trim_start()))
+}
+
+/// Returns `true` if `input` denotes the unit symbol `symbol`, accepting 'u'
+/// and both variants of 'µ' (micro sign and greek letter mu) as alias for
+/// the prefix micro.
+pub(crate) fn symbol_matches(symbol: &str, input: &str) -> bool {
+    const MICRO_PREFIXES: [char; 2] = ['\u{b5}', '\u{3bc}'];
+    if symbol == input {
+        return true;
+    }
+    match (
+        symbol.strip_prefix(MICRO_PREFIXES),
+        input.strip_prefix(['u', MICRO_PREFIXES[0], MICRO_PREFIXES[1]]),
+    ) {
+        (Some(sym), Some(inp)) => sym == inp,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Kilogram, "kg", KILO)]
+    #[unit(Gram, "g", NONE, 0.001)]
+    #[unit(Microgram, "µg", MICRO, 0.000000001)]
+    struct Mass {}
+
+    #[test]
+    fn test_split_qty_lit() {
+        assert_eq!(split_qty_lit(" 17.4 kg "), Ok(("17.4", "kg")));
+        assert_eq!(split_qty_lit("-250g"), Ok(("-250", "g")));
+        assert_eq!(split_qty_lit("+1e3m"), Ok(("+1e3", "m")));
+        assert_eq!(split_qty_lit("2.5E-3 m"), Ok(("2.5E-3", "m")));
+        // 'e' not followed by digits starts the unit symbol
+        assert_eq!(split_qty_lit("3 em"), Ok(("3", "em")));
+        assert_eq!(split_qty_lit("3em"), Ok(("3", "em")));
+        assert_eq!(split_qty_lit("3e+x"), Ok(("3", "e+x")));
+        assert_eq!(split_qty_lit("7"), Ok(("7", "")));
+        assert_eq!(split_qty_lit(""), Err(ParseQuantityError::EmptyInput));
+        assert_eq!(split_qty_lit(" \t"), Err(ParseQuantityError::EmptyInput));
+        assert_eq!(
+            split_qty_lit("kg"),
+            Err(ParseQuantityError::InvalidNumber)
+        );
+    }
+
+    #[test]
+    fn test_symbol_matches() {
+        assert!(symbol_matches("kg", "kg"));
+        assert!(!symbol_matches("kg", "g"));
+        assert!(symbol_matches("\u{b5}g", "ug"));
+        assert!(symbol_matches("\u{b5}g", "\u{3bc}g"));
+        assert!(symbol_matches("\u{3bc}g", "\u{b5}g"));
+        assert!(!symbol_matches("\u{b5}g", "um"));
+        assert!(!symbol_matches("ug", "\u{b5}g"));
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        let m: Mass = "17.4 kg".parse().unwrap();
+        assert_eq!(m, Amnt!(17.4) * KILOGRAM);
+        let m = Mass::try_from("  -250g ").unwrap();
+        assert_eq!(m, Amnt!(-250.) * GRAM);
+        let m: Mass = "1e3 g".parse().unwrap();
+        assert_eq!(m, Amnt!(1000.) * GRAM);
+        let m: Mass<i64> = "+1500g".parse().unwrap();
+        assert_eq!(m, 1500_i64 * GRAM);
+        let m: Mass = "5 ug".parse().unwrap();
+        assert_eq!(m, Amnt!(5.) * MICROGRAM);
+        let m: Mass = "5 \u{3bc}g".parse().unwrap();
+        assert_eq!(m, Amnt!(5.) * MICROGRAM);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Mass>(), Err(ParseQuantityError::EmptyInput));
+        assert_eq!(
+            "1.2.3 kg".parse::<Mass>(),
+            Err(ParseQuantityError::InvalidNumber)
+        );
+        assert_eq!(
+            "2.5 kg".parse::<Mass<i32>>(),
+            Err(ParseQuantityError::InvalidNumber)
+        );
+        assert_eq!(
+            "7 m".parse::<Mass>(),
+            Err(ParseQuantityError::UnknownUnit)
+        );
+        assert_eq!("7".parse::<Mass>(), Err(ParseQuantityError::UnknownUnit));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            ParseQuantityError::UnknownUnit.to_string(),
+            "unknown unit in quantity literal"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use crate::{HasRefUnit, LinearScaledUnit, Quantity, QuantityValue, Unit};
+
+/// Trait for quantities whose values denote points on a scale, like absolute
+/// temperatures, rather than amounts.
+///
+/// The difference between two points is an instance of the associated
+/// quantity `Interval`, which can be added to or subtracted from a point,
+/// giving a point. Points can't be added to each other.
+///
+/// A quantity defined by the proc-macro `quantity` is a `PointQuantity`, if
+/// the quantity of its intervals is given as argument `interval` of the
+/// attribute, for example `#[quantity(interval = TemperatureInterval)]`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "temperature")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::{temperature::*, temperature_interval};
+///
+/// let t1 = Amnt!(21.5) * DEGREE_CELSIUS;
+/// let t2 = Amnt!(290.15) * KELVIN;
+/// let d = t1 - t2;
+/// assert_eq!(d, Amnt!(4.5) * temperature_interval::KELVIN);
+/// assert_eq!(t2 + d, t1);
+/// # }
+/// ```
+///
+/// Adding two points fails to compile:
+///
+/// ```compile_fail
+/// use quantities::prelude::*;
+/// use quantities::si::temperature::*;
+///
+/// let t = Amnt!(20.) * DEGREE_CELSIUS + Amnt!(5.) * DEGREE_CELSIUS;
+/// ```
+pub trait PointQuantity: HasRefUnit
+where
+    <Self as Quantity>::UnitType: LinearScaledUnit,
+    <Self::Interval as Quantity>::UnitType: LinearScaledUnit,
+{
+    /// Quantity representing the difference between two points.
+    type Interval: HasRefUnit<Value = Self::Value>;
+
+    /// Returns the unit of `Self::Interval` corresponding to `unit`, i.e. the
+    /// unit with the same symbol, if there is one, otherwise the unit with the
+    /// same scale, or - if there is none - the reference unit.
+    #[must_use]
+    fn interval_unit(
+        unit: Self::UnitType,
+    ) -> <Self::Interval as Quantity>::UnitType {
+        let mut units = <Self::Interval as Quantity>::UnitType::iter();
+        units
+            .find(|u| u.symbol() == unit.symbol())
+            .copied()
+            .or_else(|| Self::Interval::unit_from_scale(unit.scale()))
+            .unwrap_or(Self::Interval::REF_UNIT)
+    }
+
+    /// Returns the interval from `rhs` to `self`, given in the unit
+    /// corresponding to the unit of `self`.
+    fn diff(self, rhs: Self) -> Self::Interval {
+        let unit = Self::interval_unit(self.unit());
+        let value = (self.value() - rhs.equiv_amount(self.unit()))
+            .mul_amount(self.unit().scale() / unit.scale());
+        Self::Interval::new(value, unit)
+    }
+
+    /// Returns the point `self` shifted by `rhs`.
+    fn add_interval(self, rhs: Self::Interval) -> Self {
+        let value =
+            rhs.value().mul_amount(rhs.unit().scale() / self.unit().scale());
+        Self::new(self.value() + value, self.unit())
+    }
+
+    /// Returns the point `self` shifted by -`rhs`.
+    fn sub_interval(self, rhs: Self::Interval) -> Self {
+        let value =
+            rhs.value().mul_amount(rhs.unit().scale() / self.unit().scale());
+        Self::new(self.value() - value, self.unit())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    mod interval {
+        use crate::prelude::*;
+
+        #[quantity]
+        #[ref_unit(Kelvin, "K", NONE)]
+        #[unit(Degree_Celsius, "°C", 1)]
+        #[unit(Degree_Fahrenheit, "°F", 0.5555555555555556)]
+        pub struct TempInterval {}
+    }
+
+    use interval::TempInterval;
+
+    #[quantity(interval = TempInterval)]
+    #[ref_unit(Kelvin, "K", NONE)]
+    #[unit(Millikelvin, "mK", 0.001)]
+    #[unit(Degree_Celsius, "°C", 1.0, 273.15)]
+    #[unit(Degree_Rankine, "°R", 0.5555555555555556)]
+    #[unit(Degree_Fahrenheit, "°F", 0.5555555555555556, 255.3722222222222)]
+    struct Temp {}
+
+    // Asserts that `q` and `r` have the same unit and approximately the same
+    // value (conversions involving a scale like 5/9 can't be exact).
+    fn assert_approx_eq<Q>(q: Q, r: Q)
+    where
+        Q: HasRefUnit<Value = Amount> + core::fmt::Display,
+        Q::UnitType: LinearScaledUnit,
+    {
+        let diff = q.value() - r.equiv_amount(q.unit());
+        let eps = Amnt!(0.000000001);
+        assert!(-eps < diff && diff < eps, "{} != {}", q, r);
+        assert!(q.unit() == r.unit(), "{} != {}", q.unit(), r.unit());
+    }
+
+    #[test]
+    fn test_interval_unit() {
+        // same symbol
+        assert_eq!(<Temp>::interval_unit(KELVIN), interval::KELVIN);
+        assert_eq!(
+            <Temp>::interval_unit(DEGREE_CELSIUS),
+            interval::DEGREE_CELSIUS
+        );
+        assert_eq!(
+            <Temp>::interval_unit(DEGREE_FAHRENHEIT),
+            interval::DEGREE_FAHRENHEIT
+        );
+        // same scale
+        assert_eq!(
+            <Temp>::interval_unit(DEGREE_RANKINE),
+            interval::DEGREE_FAHRENHEIT
+        );
+        // neither => reference unit
+        assert_eq!(<Temp>::interval_unit(MILLIKELVIN), interval::KELVIN);
+    }
+
+    #[test]
+    fn test_diff() {
+        let t1 = Amnt!(20.0) * DEGREE_CELSIUS;
+        let t2 = Amnt!(283.15) * KELVIN;
+        let d = t1.diff(t2);
+        assert_eq!(d.unit(), interval::DEGREE_CELSIUS);
+        assert_eq!(d, Amnt!(10.0) * interval::KELVIN);
+        assert_eq!(t2.diff(t1), Amnt!(-10.0) * interval::KELVIN);
+        assert_eq!(t1 - t2, d);
+        let d = (Amnt!(50.0) * DEGREE_FAHRENHEIT) - t1;
+        assert_eq!(d.unit(), interval::DEGREE_FAHRENHEIT);
+        assert_approx_eq(d, Amnt!(-18.0) * interval::DEGREE_FAHRENHEIT);
+        let d = (Amnt!(500.) * MILLIKELVIN) - (Amnt!(0.25) * KELVIN);
+        assert_eq!(d.unit(), interval::KELVIN);
+        assert_eq!(d, Amnt!(0.25) * interval::KELVIN);
+    }
+
+    #[test]
+    fn test_add_sub_interval() {
+        let t1 = Amnt!(20.0) * DEGREE_CELSIUS;
+        let t2 = Amnt!(283.15) * KELVIN;
+        let d = Amnt!(10.0) * interval::DEGREE_CELSIUS;
+        assert_eq!(t2.add_interval(d), Amnt!(293.15) * KELVIN);
+        assert_eq!(t2 + d, Amnt!(293.15) * KELVIN);
+        assert_eq!(d + t2, Amnt!(293.15) * KELVIN);
+        assert_eq!(t1.sub_interval(d), Amnt!(10.0) * DEGREE_CELSIUS);
+        assert_eq!(t1 - d, Amnt!(10.0) * DEGREE_CELSIUS);
+        let t3 = t1 + Amnt!(9.0) * interval::DEGREE_FAHRENHEIT;
+        assert_eq!(t3.unit(), DEGREE_CELSIUS);
+        assert_approx_eq(t3, Amnt!(25.0) * DEGREE_CELSIUS);
+        let t4 = (Amnt!(1.) * KELVIN) - (Amnt!(1.5) * interval::KELVIN);
+        assert_eq!(t4, Amnt!(-0.5) * KELVIN);
+    }
+}
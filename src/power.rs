@@ -0,0 +1,154 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use crate::Quantity;
+
+/// Trait for quantities which can be raised to the integral power `N`,
+/// giving a quantity of type `Output`.
+///
+/// The proc-macro `quantity` generates impls of `QtyPow<2>` and `QtyPow<3>`
+/// for each formula defining a quantity as the square or the cube of
+/// another quantity, for example `#[quantity(Length * Length)]` or
+/// `#[quantity(Length^3)]`.
+///
+/// The unit of the result is the unit whose scale equals the scale of the
+/// given unit raised to the power `N` (i. e. km => km²). If there is no such
+/// unit, the result is converted to the best-fitting unit.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "volume")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::{area::*, length::*, volume::*};
+///
+/// let len = Amnt!(3.) * KILOMETER;
+/// let area: Area = len.powi::<2>();
+/// assert_eq!(area, Amnt!(9.) * SQUARE_KILOMETER);
+/// let vol: Volume = (Amnt!(2.) * METER).powi::<3>();
+/// assert_eq!(vol, Amnt!(8.) * CUBIC_METER);
+/// # }
+/// ```
+pub trait QtyPow<const N: i32>: Quantity {
+    /// Quantity resulting from raising `Self` to the power `N`.
+    type Output: Quantity<Value = Self::Value>;
+
+    /// Returns `self` raised to the power `N`.
+    fn pow(self) -> Self::Output;
+}
+
+/// Trait for quantities whose root of degree `N` is a quantity of type
+/// `Output`.
+///
+/// The proc-macro `quantity` generates impls of `QtyRoot<2>` and
+/// `QtyRoot<3>` for each formula defining a quantity as the square or the
+/// cube of another quantity, i. e. inverting the impls of [QtyPow].
+///
+/// The unit of the result is the unit whose scale raised to the power `N`
+/// equals the scale of the given unit (i. e. km² => km). If there is no such
+/// unit, the result is converted to the best-fitting unit.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "volume")]
+/// # {
+/// use quantities::prelude::*;
+/// use quantities::si::{area::*, length::*, volume::*};
+///
+/// let area = Amnt!(6.25) * SQUARE_KILOMETER;
+/// assert_eq!(area.sqrt(), Amnt!(2.5) * KILOMETER);
+/// let vol = Amnt!(27.) * CUBIC_CENTIMETER;
+/// assert_eq!(vol.cbrt(), Amnt!(3.) * CENTIMETER);
+/// # }
+/// ```
+pub trait QtyRoot<const N: i32>: Quantity {
+    /// Quantity resulting from taking the root of degree `N` of `Self`.
+    type Output: Quantity<Value = Self::Value>;
+
+    /// Returns the root of degree `N` of `self`.
+    fn root(self) -> Self::Output;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Meter, "m", NONE, prefixes = [CENTI, KILO])]
+    #[unit(Foot, "ft", 0.3048)]
+    struct Length {}
+
+    #[quantity(Length * Length)]
+    #[ref_unit(Square_Meter, "m²", NONE)]
+    #[unit(Square_Centimeter, "cm²", 0.0001)]
+    #[unit(Hectare, "ha", 10000)]
+    #[unit(Square_Kilometer, "km²", 1000000)]
+    struct Area {}
+
+    #[quantity(Length^3)]
+    #[ref_unit(Cubic_Meter, "m³", NONE)]
+    #[unit(Liter, "l", 0.001)]
+    #[unit(Cubic_Kilometer, "km³", 1000000000)]
+    struct Volume {}
+
+    #[test]
+    fn test_pow() {
+        let area: Area = (Amnt!(1.5) * KILOMETER).powi::<2>();
+        assert_eq!(area.unit(), SQUARE_KILOMETER);
+        assert_eq!(area.value(), Amnt!(2.25));
+        let vol: Volume = (Amnt!(-2.) * KILOMETER).powi::<3>();
+        assert_eq!(vol.unit(), CUBIC_KILOMETER);
+        assert_eq!(vol.value(), Amnt!(-8.));
+        // there's no unit of area whose scale is the square of a foot
+        let area: Area = (Amnt!(100.) * FOOT).powi::<2>();
+        let diff = area.equiv_amount(SQUARE_METER) - Amnt!(929.0304);
+        assert!(diff.abs() < Amnt!(1e-9));
+        // there's no unit of volume whose scale is the cube of a centimeter
+        let vol: Volume = (Amnt!(10.) * CENTIMETER).powi::<3>();
+        let diff = vol.equiv_amount(LITER) - Amnt!(1.);
+        assert!(diff.abs() < Amnt!(1e-9));
+    }
+
+    #[test]
+    fn test_root() {
+        let len = (Amnt!(2.25) * SQUARE_KILOMETER).sqrt();
+        assert_eq!(len.unit(), KILOMETER);
+        assert_eq!(len.value(), Amnt!(1.5));
+        let len = (Amnt!(-8.) * CUBIC_KILOMETER).cbrt();
+        assert_eq!(len.unit(), KILOMETER);
+        assert_eq!(len.value(), Amnt!(-2.));
+        // there's no unit of length whose square is a hectare
+        let len = (Amnt!(4.) * HECTARE).sqrt();
+        assert_eq!(len.unit(), METER);
+        assert_eq!(len.value(), Amnt!(200.));
+        let len = (Amnt!(3.) * SQUARE_METER).sqrt();
+        let diff = len.value() - Amnt!(1.732050807568877);
+        assert!(diff.abs() < Amnt!(1e-15));
+        // there's no unit of length whose cube is a liter
+        let len = (Amnt!(8.) * LITER).cbrt();
+        assert_eq!(len.unit(), CENTIMETER);
+        let diff = len.value() - Amnt!(20.);
+        assert!(diff.abs() < Amnt!(1e-9));
+    }
+
+    #[test]
+    fn test_value_types() {
+        let len = Length::<i64>::new(16, METER);
+        let area: Area<i64> = len.powi::<2>();
+        assert_eq!(area.value(), 256);
+        assert_eq!(area.sqrt().value(), 16);
+        assert_eq!(Area::<i64>::new(20, SQUARE_METER).sqrt().value(), 4);
+        let vol = Volume::<f32>::new(0.125, CUBIC_METER);
+        assert_eq!(vol.cbrt().value(), 0.5);
+        let vol: Volume<f64> = (3.0_f64 * METER).powi::<3>();
+        assert_eq!(vol, 27.0 * CUBIC_METER);
+    }
+}
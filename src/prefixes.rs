@@ -15,6 +15,10 @@ use ::qty_macros::EnumIter;
 /// of the original unit.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter)]
 pub enum SIPrefix {
+    /// 10⁻³⁰
+    QUECTO = -30,
+    /// 10⁻²⁷
+    RONTO = -27,
     /// 10⁻²⁴
     YOCTO = -24,
     /// 10⁻²¹
@@ -57,6 +61,10 @@ pub enum SIPrefix {
     ZETTA = 21,
     /// 10²⁴
     YOTTA = 24,
+    /// 10²⁷
+    RONNA = 27,
+    /// 10³⁰
+    QUETTA = 30,
 }
 
 impl SIPrefix {
@@ -64,6 +72,8 @@ impl SIPrefix {
     #[must_use]
     pub const fn name(&self) -> &'static str {
         match self {
+            Self::QUECTO => "Quecto",
+            Self::RONTO => "Ronto",
             Self::YOCTO => "Yocto",
             Self::ZEPTO => "Zepto",
             Self::ATTO => "Atto",
@@ -85,6 +95,8 @@ impl SIPrefix {
             Self::EXA => "Exa",
             Self::ZETTA => "Zetta",
             Self::YOTTA => "Yotta",
+            Self::RONNA => "Ronna",
+            Self::QUETTA => "Quetta",
         }
     }
 
@@ -92,6 +104,8 @@ impl SIPrefix {
     #[must_use]
     pub const fn abbr(&self) -> &'static str {
         match self {
+            Self::QUECTO => "q",
+            Self::RONTO => "r",
             Self::YOCTO => "y",
             Self::ZEPTO => "z",
             Self::ATTO => "a",
@@ -113,6 +127,8 @@ impl SIPrefix {
             Self::EXA => "E",
             Self::ZETTA => "Z",
             Self::YOTTA => "Y",
+            Self::RONNA => "R",
+            Self::QUETTA => "Q",
         }
     }
 
@@ -128,6 +144,8 @@ impl SIPrefix {
     #[must_use]
     pub fn from_abbr(abbr: &str) -> Option<Self> {
         match abbr {
+            "q" => Some(Self::QUECTO),
+            "r" => Some(Self::RONTO),
             "y" => Some(Self::YOCTO),
             "z" => Some(Self::ZEPTO),
             "a" => Some(Self::ATTO),
@@ -149,6 +167,8 @@ impl SIPrefix {
             "E" => Some(Self::EXA),
             "Z" => Some(Self::ZETTA),
             "Y" => Some(Self::YOTTA),
+            "R" => Some(Self::RONNA),
+            "Q" => Some(Self::QUETTA),
             _ => None,
         }
     }
@@ -158,6 +178,8 @@ impl SIPrefix {
     #[must_use]
     pub const fn from_exp(exp: i8) -> Option<Self> {
         match exp {
+            -30 => Some(Self::QUECTO),
+            -27 => Some(Self::RONTO),
             -24 => Some(Self::YOCTO),
             -21 => Some(Self::ZEPTO),
             -18 => Some(Self::ATTO),
@@ -179,6 +201,124 @@ impl SIPrefix {
             18 => Some(Self::EXA),
             21 => Some(Self::ZETTA),
             24 => Some(Self::YOTTA),
+            27 => Some(Self::RONNA),
+            30 => Some(Self::QUETTA),
+            _ => None,
+        }
+    }
+}
+
+/// Enum of binary unit prefixes defined by the IEC.
+///
+/// These prefixes can be added to unit names to name multiples of the
+/// original unit by powers of 2 (mainly used for units of information like
+/// Byte).
+///
+/// Units with binary prefixes can be defined via attribute macro `quantity`
+/// by giving the prefix instead of a SI prefix or by generating them from the
+/// unprefixed unit:
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// #[quantity]
+/// #[ref_unit(Byte, "B", NONE, prefixes = [KILO..=GIGA, KIBI..=GIBI])]
+/// #[unit(Bit, "b", 0.125)]
+/// struct Memory {}
+///
+/// assert_eq!(MEBIBYTE.symbol(), "MiB");
+/// assert_eq!(MEBIBYTE.binary_prefix(), Some(BinaryPrefix::MEBI));
+/// assert_eq!(MEBIBYTE.si_prefix(), None);
+/// assert_eq!(KIBIBYTE.scale(), Amnt!(1024.));
+/// assert_eq!(Amnt!(2.) * GIBIBYTE, Amnt!(2048.) * MEBIBYTE);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter)]
+pub enum BinaryPrefix {
+    /// 2¹⁰
+    KIBI = 10,
+    /// 2²⁰
+    MEBI = 20,
+    /// 2³⁰
+    GIBI = 30,
+    /// 2⁴⁰
+    TEBI = 40,
+    /// 2⁵⁰
+    PEBI = 50,
+    /// 2⁶⁰
+    EXBI = 60,
+    /// 2⁷⁰
+    ZEBI = 70,
+    /// 2⁸⁰
+    YOBI = 80,
+}
+
+impl BinaryPrefix {
+    /// Returns the name of `self`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::KIBI => "Kibi",
+            Self::MEBI => "Mebi",
+            Self::GIBI => "Gibi",
+            Self::TEBI => "Tebi",
+            Self::PEBI => "Pebi",
+            Self::EXBI => "Exbi",
+            Self::ZEBI => "Zebi",
+            Self::YOBI => "Yobi",
+        }
+    }
+
+    /// Returns the abbreviation used to represent `self`.
+    #[must_use]
+    pub const fn abbr(&self) -> &'static str {
+        match self {
+            Self::KIBI => "Ki",
+            Self::MEBI => "Mi",
+            Self::GIBI => "Gi",
+            Self::TEBI => "Ti",
+            Self::PEBI => "Pi",
+            Self::EXBI => "Ei",
+            Self::ZEBI => "Zi",
+            Self::YOBI => "Yi",
+        }
+    }
+
+    /// Returns the exponent of base 2 represented by `self`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn exp(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the binary prefix with the abbreviation `abbr`, or `None` if
+    /// there is no such binary prefix.
+    #[must_use]
+    pub fn from_abbr(abbr: &str) -> Option<Self> {
+        match abbr {
+            "Ki" => Some(Self::KIBI),
+            "Mi" => Some(Self::MEBI),
+            "Gi" => Some(Self::GIBI),
+            "Ti" => Some(Self::TEBI),
+            "Pi" => Some(Self::PEBI),
+            "Ei" => Some(Self::EXBI),
+            "Zi" => Some(Self::ZEBI),
+            "Yi" => Some(Self::YOBI),
+            _ => None,
+        }
+    }
+
+    /// Returns the binary prefix with the exponent `exp`, or `None` if there
+    /// is no such binary prefix.
+    #[must_use]
+    pub const fn from_exp(exp: u8) -> Option<Self> {
+        match exp {
+            10 => Some(Self::KIBI),
+            20 => Some(Self::MEBI),
+            30 => Some(Self::GIBI),
+            40 => Some(Self::TEBI),
+            50 => Some(Self::PEBI),
+            60 => Some(Self::EXBI),
+            70 => Some(Self::ZEBI),
+            80 => Some(Self::YOBI),
             _ => None,
         }
     }
@@ -190,29 +330,34 @@ mod tests {
 
     #[test]
     fn test_iter() {
-        let mut it = SIPrefix::iter();
-        assert_eq!(it.next(), Some(&SIPrefix::YOCTO));
-        assert_eq!(it.next(), Some(&SIPrefix::ZEPTO));
-        assert_eq!(it.next(), Some(&SIPrefix::ATTO));
-        assert_eq!(it.next(), Some(&SIPrefix::FEMTO));
-        assert_eq!(it.next(), Some(&SIPrefix::PICO));
-        assert_eq!(it.next(), Some(&SIPrefix::NANO));
-        assert_eq!(it.next(), Some(&SIPrefix::MICRO));
-        assert_eq!(it.next(), Some(&SIPrefix::MILLI));
-        assert_eq!(it.next(), Some(&SIPrefix::CENTI));
-        assert_eq!(it.next(), Some(&SIPrefix::DECI));
-        assert_eq!(it.next(), Some(&SIPrefix::NONE));
-        assert_eq!(it.next(), Some(&SIPrefix::DECA));
-        assert_eq!(it.next(), Some(&SIPrefix::HECTO));
-        assert_eq!(it.next(), Some(&SIPrefix::KILO));
-        assert_eq!(it.next(), Some(&SIPrefix::MEGA));
-        assert_eq!(it.next(), Some(&SIPrefix::GIGA));
-        assert_eq!(it.next(), Some(&SIPrefix::TERA));
-        assert_eq!(it.next(), Some(&SIPrefix::PETA));
-        assert_eq!(it.next(), Some(&SIPrefix::EXA));
-        assert_eq!(it.next(), Some(&SIPrefix::ZETTA));
-        assert_eq!(it.next(), Some(&SIPrefix::YOTTA));
-        assert_eq!(it.next(), None);
+        let prefixes = [
+            SIPrefix::QUECTO,
+            SIPrefix::RONTO,
+            SIPrefix::YOCTO,
+            SIPrefix::ZEPTO,
+            SIPrefix::ATTO,
+            SIPrefix::FEMTO,
+            SIPrefix::PICO,
+            SIPrefix::NANO,
+            SIPrefix::MICRO,
+            SIPrefix::MILLI,
+            SIPrefix::CENTI,
+            SIPrefix::DECI,
+            SIPrefix::NONE,
+            SIPrefix::DECA,
+            SIPrefix::HECTO,
+            SIPrefix::KILO,
+            SIPrefix::MEGA,
+            SIPrefix::GIGA,
+            SIPrefix::TERA,
+            SIPrefix::PETA,
+            SIPrefix::EXA,
+            SIPrefix::ZETTA,
+            SIPrefix::YOTTA,
+            SIPrefix::RONNA,
+            SIPrefix::QUETTA,
+        ];
+        assert!(SIPrefix::iter().eq(prefixes.iter()));
     }
 
     #[test]
@@ -228,6 +373,8 @@ mod tests {
         assert_eq!(SIPrefix::from_abbr("M").unwrap(), SIPrefix::MEGA);
         assert_eq!(SIPrefix::from_abbr("µ").unwrap(), SIPrefix::MICRO);
         assert_eq!(SIPrefix::from_abbr("Z").unwrap(), SIPrefix::ZETTA);
+        assert_eq!(SIPrefix::from_abbr("q").unwrap(), SIPrefix::QUECTO);
+        assert_eq!(SIPrefix::from_abbr("R").unwrap(), SIPrefix::RONNA);
         assert!(SIPrefix::from_abbr("x").is_none());
     }
 
@@ -236,6 +383,23 @@ mod tests {
         assert_eq!(SIPrefix::from_exp(-18).unwrap(), SIPrefix::ATTO);
         assert_eq!(SIPrefix::from_exp(0).unwrap(), SIPrefix::NONE);
         assert_eq!(SIPrefix::from_exp(9).unwrap(), SIPrefix::GIGA);
+        assert_eq!(SIPrefix::from_exp(-27).unwrap(), SIPrefix::RONTO);
+        assert_eq!(SIPrefix::from_exp(30).unwrap(), SIPrefix::QUETTA);
         assert!(SIPrefix::from_exp(7).is_none());
     }
+
+    #[test]
+    fn test_binary_prefix() {
+        let mut it = BinaryPrefix::iter();
+        assert_eq!(it.next(), Some(&BinaryPrefix::KIBI));
+        assert_eq!(it.last(), Some(&BinaryPrefix::YOBI));
+        let mi = BinaryPrefix::MEBI;
+        assert_eq!(mi.name(), "Mebi");
+        assert_eq!(mi.abbr(), "Mi");
+        assert_eq!(mi.exp(), 20);
+        assert_eq!(BinaryPrefix::from_abbr("Gi"), Some(BinaryPrefix::GIBI));
+        assert!(BinaryPrefix::from_abbr("G").is_none());
+        assert_eq!(BinaryPrefix::from_exp(40), Some(BinaryPrefix::TEBI));
+        assert!(BinaryPrefix::from_exp(3).is_none());
+    }
 }
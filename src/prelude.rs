@@ -14,13 +14,23 @@ pub use core::cmp::Ordering;
 #[doc(hidden)]
 pub use core::fmt;
 #[doc(hidden)]
-pub use core::ops::{Add, Div, Mul, Sub};
+pub use core::iter::Sum;
+#[doc(hidden)]
+pub use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign,
+};
 
 pub use qty_macros::quantity;
 
+#[doc(hidden)]
+pub use crate::dimension as qty_dimension;
+#[doc(hidden)]
+pub use crate::{impl_mul_value_unit, impl_serde};
 pub use crate::{
-    Amount, HasRefUnit, LinearScaledUnit, Quantity, QuantityImpl, Rate, SIPrefix,
-    Unit, ONE,
+    AdditiveQuantity, Amnt, Amount, AnyQuantity, BinaryPrefix,
+    DimensionError, HasRefUnit, LinearScaledUnit, ParseQuantityError,
+    PointQuantity, QtyPow, QtyRoot, Quantity, QuantityImpl, QuantityValue,
+    Rate, SIPrefix, Unit, ONE,
 };
 #[cfg(feature = "fpdec")]
 pub use crate::{Dec, Decimal};
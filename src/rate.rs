@@ -12,24 +12,24 @@ use core::{
     ops::{Div, Mul},
 };
 
-use crate::{Amount, Quantity, Unit, AMNT_ONE};
+use crate::{Quantity, QuantityValue, Unit};
 
 /// The ratio between two related quantity values.
 #[derive(Copy, Clone, Debug)]
-pub struct Rate<TQ: Quantity, PQ: Quantity> {
-    term_amount: Amount,
+pub struct Rate<TQ: Quantity, PQ: Quantity<Value = TQ::Value>> {
+    term_amount: TQ::Value,
     term_unit: TQ::UnitType,
-    per_unit_multiple: Amount,
+    per_unit_multiple: TQ::Value,
     per_unit: PQ::UnitType,
 }
 
-impl<TQ: Quantity, PQ: Quantity> Rate<TQ, PQ> {
+impl<TQ: Quantity, PQ: Quantity<Value = TQ::Value>> Rate<TQ, PQ> {
     /// Returns a new instance of `Rate` with attributes equal to given params.
     #[inline(always)]
     pub const fn new(
-        term_amount: Amount,
+        term_amount: TQ::Value,
         term_unit: TQ::UnitType,
-        per_unit_multiple: Amount,
+        per_unit_multiple: TQ::Value,
         per_unit: PQ::UnitType,
     ) -> Self {
         Self {
@@ -54,7 +54,7 @@ impl<TQ: Quantity, PQ: Quantity> Rate<TQ, PQ> {
 
     /// Returns the term amount of `self`.
     #[inline(always)]
-    pub const fn term_amount(&self) -> Amount {
+    pub const fn term_amount(&self) -> TQ::Value {
         self.term_amount
     }
 
@@ -66,7 +66,7 @@ impl<TQ: Quantity, PQ: Quantity> Rate<TQ, PQ> {
 
     /// Returns the per unit multiple of `self`.
     #[inline(always)]
-    pub const fn per_unit_multiple(&self) -> Amount {
+    pub const fn per_unit_multiple(&self) -> TQ::Value {
         self.per_unit_multiple
     }
 
@@ -87,7 +87,9 @@ impl<TQ: Quantity, PQ: Quantity> Rate<TQ, PQ> {
     }
 }
 
-impl<TQ: Quantity, PQ: Quantity> fmt::Display for Rate<TQ, PQ> {
+impl<TQ: Quantity, PQ: Quantity<Value = TQ::Value>> fmt::Display
+    for Rate<TQ, PQ>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.term_unit().symbol() == "" {
             write!(f, "{} / ", self.term_amount())?;
@@ -101,7 +103,7 @@ impl<TQ: Quantity, PQ: Quantity> fmt::Display for Rate<TQ, PQ> {
         };
         if self.per_unit().symbol() == "" {
             write!(f, "{}", self.per_unit_multiple())
-        } else if self.per_unit_multiple() == AMNT_ONE {
+        } else if self.per_unit_multiple() == TQ::Value::ONE {
             write!(f, "{}", self.per_unit().symbol())
         } else {
             write!(
@@ -114,15 +116,15 @@ impl<TQ: Quantity, PQ: Quantity> fmt::Display for Rate<TQ, PQ> {
     }
 }
 
-impl<TQ: Quantity, PQ: Quantity> Mul<PQ> for Rate<TQ, PQ>
+impl<TQ: Quantity, PQ: Quantity<Value = TQ::Value>> Mul<PQ> for Rate<TQ, PQ>
 where
-    PQ: Div<PQ, Output = Amount>,
+    PQ: Div<PQ, Output = TQ::Value>,
 {
     type Output = TQ;
 
     fn mul(self, rhs: PQ) -> Self::Output {
-        let amnt: Amount =
-            (rhs / self.per_unit().as_qty()) / self.per_unit_multiple();
+        let amnt: TQ::Value = (rhs / PQ::new(TQ::Value::ONE, self.per_unit()))
+            / self.per_unit_multiple();
         Self::Output::new(amnt * self.term_amount(), self.term_unit())
     }
 }
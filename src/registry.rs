@@ -0,0 +1,420 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! This module contains a registry for units defined at runtime.
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+use crate::{
+    parse, Amount, HasRefUnit, LinearScaledUnit, ParseQuantityError,
+    QuantityValue, AMNT_ZERO,
+};
+
+/// A unit registered at runtime, defined by its name, its symbol and its
+/// scale and offset in terms of the reference unit of a quantity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicUnit {
+    name: String,
+    symbol: String,
+    scale: Amount,
+    offset: Amount,
+}
+
+impl DynamicUnit {
+    /// Returns the name of `self`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the symbol used to represent `self`.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Returns `factor` so that `factor` * `REF_UNIT` == 1 * `self`.
+    #[must_use]
+    #[allow(clippy::double_must_use)]
+    pub const fn scale(&self) -> Amount {
+        self.scale
+    }
+
+    /// Returns `offset` so that `x` * `self` ==
+    /// (`x` * `self.scale()` + `offset`) * `REF_UNIT`.
+    #[must_use]
+    #[allow(clippy::double_must_use)]
+    pub const fn offset(&self) -> Amount {
+        self.offset
+    }
+}
+
+/// A unit held by a `UnitRegistry`, i.e. either one of the units of the
+/// quantity defined at compile time or a unit registered at runtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegisteredUnit<'a, U: LinearScaledUnit> {
+    /// A unit defined at compile time.
+    Static(U),
+    /// A unit registered at runtime.
+    Dynamic(&'a DynamicUnit),
+}
+
+impl<'a, U: LinearScaledUnit> RegisteredUnit<'a, U> {
+    /// Returns the name of `self`.
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        match self {
+            Self::Static(unit) => unit.name(),
+            Self::Dynamic(unit) => unit.name(),
+        }
+    }
+
+    /// Returns the symbol used to represent `self`.
+    #[must_use]
+    pub fn symbol(&self) -> &'a str {
+        match self {
+            Self::Static(unit) => unit.symbol(),
+            Self::Dynamic(unit) => unit.symbol(),
+        }
+    }
+
+    /// Returns `factor` so that `factor` * `U::REF_UNIT` == 1 * `self`.
+    pub fn scale(&self) -> Amount {
+        match self {
+            Self::Static(unit) => unit.scale(),
+            Self::Dynamic(unit) => unit.scale(),
+        }
+    }
+
+    /// Returns `offset` so that `x` * `self` ==
+    /// (`x` * `self.scale()` + `offset`) * `U::REF_UNIT`.
+    pub fn offset(&self) -> Amount {
+        match self {
+            Self::Static(unit) => unit.offset(),
+            Self::Dynamic(unit) => unit.offset(),
+        }
+    }
+
+    /// Returns the quantity equivalent to `value` * `self`.
+    ///
+    /// If `self` is a unit registered at runtime, the result is given in the
+    /// reference unit of `Q`.
+    #[must_use]
+    pub fn to_qty<Q>(&self, value: Q::Value) -> Q
+    where
+        Q: HasRefUnit<UnitType = U>,
+    {
+        match self {
+            Self::Static(unit) => Q::new(value, *unit),
+            Self::Dynamic(unit) => {
+                let mut amount = value.mul_amount(unit.scale());
+                if unit.offset() != AMNT_ZERO {
+                    amount = amount + Q::Value::from_amount(unit.offset());
+                }
+                Q::new(amount, Q::REF_UNIT)
+            }
+        }
+    }
+
+    /// Returns `amount` so that `amount` * `self` == `qty`.
+    #[must_use]
+    pub fn equiv_amount<Q>(&self, qty: &Q) -> Q::Value
+    where
+        Q: HasRefUnit<UnitType = U>,
+    {
+        match self {
+            Self::Static(unit) => qty.equiv_amount(*unit),
+            Self::Dynamic(unit) => {
+                let mut amount = qty.equiv_amount(Q::REF_UNIT);
+                if unit.offset() != AMNT_ZERO {
+                    amount = amount - Q::Value::from_amount(unit.offset());
+                }
+                amount.div_amount(unit.scale())
+            }
+        }
+    }
+}
+
+impl<U: LinearScaledUnit> fmt::Display for RegisteredUnit<'_, U> {
+    #[inline(always)]
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.symbol(), form)
+    }
+}
+
+/// An error which can be returned when registering a unit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegistryError {
+    /// The given symbol is empty or already denotes a unit of the quantity.
+    DuplicateSymbol,
+    /// The given scale is not greater than zero.
+    InvalidScale,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::DuplicateSymbol => "unit symbol empty or already registered",
+            Self::InvalidScale => "unit scale must be greater than zero",
+        };
+        fmt::Display::fmt(msg, form)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RegistryError {}
+
+/// A registry holding the units of quantity `Q` defined at compile time
+/// together with units registered at runtime, for example read from a
+/// configuration file.
+///
+/// Quantities can't hold a unit registered at runtime, so values given in
+/// such a unit are converted to the reference unit of `Q`.
+///
+/// # Example
+///
+/// ```rust
+/// # use quantities::prelude::*;
+/// # use quantities::UnitRegistry;
+/// #[quantity]
+/// #[ref_unit(Liter, "l", NONE)]
+/// #[unit(Milliliter, "ml", MILLI, 0.001)]
+/// struct Volume {}
+///
+/// let mut registry = UnitRegistry::<Volume>::new();
+/// registry.register("Barrel", "bbl", Amnt!(158.987294928), None).unwrap();
+/// let unit = registry.unit_from_symbol("bbl").unwrap();
+/// assert_eq!(unit.name(), "Barrel");
+/// let vol: Volume = registry.parse("2 bbl").unwrap();
+/// assert_eq!(vol, Amnt!(317.974589856) * LITER);
+/// assert_eq!(unit.equiv_amount(&vol), Amnt!(2.0));
+/// ```
+#[derive(Debug)]
+pub struct UnitRegistry<Q>
+where
+    Q: HasRefUnit,
+    Q::UnitType: LinearScaledUnit,
+{
+    units: Vec<DynamicUnit>,
+    qty: PhantomData<Q>,
+}
+
+impl<Q> Default for UnitRegistry<Q>
+where
+    Q: HasRefUnit,
+    Q::UnitType: LinearScaledUnit,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Q> UnitRegistry<Q>
+where
+    Q: HasRefUnit,
+    Q::UnitType: LinearScaledUnit,
+{
+    /// Returns a new registry holding only the units of `Q` defined at
+    /// compile time.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            units: Vec::new(),
+            qty: PhantomData,
+        }
+    }
+
+    /// Registers the unit with the given `name` and `symbol`, defined as
+    /// `x` * unit == (`x` * `scale` + `offset`) * `Q::REF_UNIT`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegistryError::DuplicateSymbol` if `symbol` is empty or
+    /// already denotes a unit in `self`, and `RegistryError::InvalidScale`
+    /// if `scale` is not greater than zero.
+    pub fn register(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        scale: Amount,
+        offset: Option<Amount>,
+    ) -> Result<(), RegistryError> {
+        if symbol.is_empty() || self.unit_from_symbol(symbol).is_some() {
+            return Err(RegistryError::DuplicateSymbol);
+        }
+        if scale <= AMNT_ZERO {
+            return Err(RegistryError::InvalidScale);
+        }
+        self.units.push(DynamicUnit {
+            name: name.into(),
+            symbol: symbol.into(),
+            scale,
+            offset: offset.unwrap_or(AMNT_ZERO),
+        });
+        Ok(())
+    }
+
+    /// Returns an iterator over the units held by `self`, starting with the
+    /// units defined at compile time.
+    pub fn iter_units(
+        &self,
+    ) -> impl Iterator<Item = RegisteredUnit<'_, Q::UnitType>> {
+        Q::iter_units()
+            .map(|unit| RegisteredUnit::Static(*unit))
+            .chain(self.units.iter().map(RegisteredUnit::Dynamic))
+    }
+
+    /// Returns `Some(unit)` where `unit.symbol()` == `symbol`, or `None` if
+    /// there is no such unit.
+    #[must_use]
+    pub fn unit_from_symbol(
+        &self,
+        symbol: &str,
+    ) -> Option<RegisteredUnit<'_, Q::UnitType>> {
+        self.iter_units().find(|unit| unit.symbol() == symbol)
+    }
+
+    /// Returns the quantity parsed from `lit`, which must consist of a number
+    /// followed by the symbol of a unit held by `self`, optionally separated
+    /// by whitespace.
+    ///
+    /// If the unit has been registered at runtime, the result is given in
+    /// the reference unit of `Q`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `QuantityImpl::parse`.
+    pub fn parse(&self, lit: &str) -> Result<Q, ParseQuantityError>
+    where
+        Q::Value: FromStr,
+    {
+        let (num, sym) = parse::split_qty_lit(lit)?;
+        let value = num
+            .parse::<Q::Value>()
+            .map_err(|_| ParseQuantityError::InvalidNumber)?;
+        let unit = self
+            .iter_units()
+            .find(|unit| parse::symbol_matches(unit.symbol(), sym))
+            .ok_or(ParseQuantityError::UnknownUnit)?;
+        Ok(unit.to_qty(value))
+    }
+
+    /// Returns `amount` so that `amount` * `to` == `value` * `from`, or
+    /// `None` if `from` or `to` doesn't denote a unit held by `self`.
+    #[must_use]
+    pub fn convert(
+        &self,
+        value: Q::Value,
+        from: &str,
+        to: &str,
+    ) -> Option<Q::Value> {
+        let from = self.unit_from_symbol(from)?;
+        let to = self.unit_from_symbol(to)?;
+        Some(to.equiv_amount(&from.to_qty::<Q>(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Meter, "m", NONE)]
+    #[unit(Kilometer, "km", KILO, 1000)]
+    struct Level {}
+
+    // x ft == (x * 0.25 + 2) m
+    fn registry() -> UnitRegistry<Level> {
+        let mut registry = UnitRegistry::<Level>::new();
+        assert_eq!(
+            registry.register("Foot", "ft", Amnt!(0.25), Some(Amnt!(2.0))),
+            Ok(())
+        );
+        registry
+    }
+
+    #[test]
+    fn test_duplicate_symbol() {
+        let mut registry = registry();
+        assert_eq!(
+            registry.register("Feet", "ft", Amnt!(0.5), None),
+            Err(RegistryError::DuplicateSymbol)
+        );
+        assert_eq!(
+            registry.register("Kilometre", "km", Amnt!(1000.0), None),
+            Err(RegistryError::DuplicateSymbol)
+        );
+        assert_eq!(
+            registry.register("Nothing", "", Amnt!(1.0), None),
+            Err(RegistryError::DuplicateSymbol)
+        );
+        assert_eq!(registry.iter_units().count(), 3);
+    }
+
+    #[test]
+    fn test_invalid_scale() {
+        let mut registry = registry();
+        assert_eq!(
+            registry.register("Zero", "z", Amnt!(0.0), None),
+            Err(RegistryError::InvalidScale)
+        );
+        assert_eq!(
+            registry.register("Negative", "n", Amnt!(-1.0), None),
+            Err(RegistryError::InvalidScale)
+        );
+        assert!(registry.unit_from_symbol("z").is_none());
+    }
+
+    #[test]
+    fn test_unknown_symbol() {
+        let registry = registry();
+        assert!(registry.unit_from_symbol("yd").is_none());
+        assert_eq!(
+            registry.parse("3 yd"),
+            Err(ParseQuantityError::UnknownUnit)
+        );
+        assert!(registry.convert(Amnt!(1.0), "yd", "m").is_none());
+        assert!(registry.convert(Amnt!(1.0), "m", "yd").is_none());
+    }
+
+    #[test]
+    fn test_units() {
+        let registry = registry();
+        assert_eq!(
+            registry.unit_from_symbol("km"),
+            Some(RegisteredUnit::Static(KILOMETER))
+        );
+        let unit = registry.unit_from_symbol("ft").unwrap();
+        assert_eq!(unit.name(), "Foot");
+        assert_eq!(unit.symbol(), "ft");
+        assert_eq!(unit.scale(), Amnt!(0.25));
+        assert_eq!(unit.offset(), Amnt!(2.0));
+        assert_eq!(
+            registry.iter_units().last().map(|unit| unit.symbol()),
+            Some("ft")
+        );
+        assert_eq!(unit.to_qty::<Level>(Amnt!(8.0)), Amnt!(4.0) * METER);
+        assert_eq!(unit.equiv_amount(&(Amnt!(4.0) * METER)), Amnt!(8.0));
+    }
+
+    #[test]
+    fn test_parse_and_convert() {
+        let registry = registry();
+        assert_eq!(registry.parse("8 ft"), Ok(Amnt!(4.0) * METER));
+        assert_eq!(registry.parse("2km"), Ok(Amnt!(2.0) * KILOMETER));
+        assert_eq!(
+            registry.convert(Amnt!(1.0), "km", "ft"),
+            Some(Amnt!(3992.0))
+        );
+        assert_eq!(registry.convert(Amnt!(8.0), "ft", "m"), Some(Amnt!(4.0)));
+        assert_eq!(registry.convert(Amnt!(8.0), "ft", "ft"), Some(Amnt!(8.0)));
+    }
+}
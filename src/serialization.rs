@@ -0,0 +1,568 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+//! Support for serializing and deserializing quantities, units and rates
+//! using `serde`.
+//!
+//! Units are serialized by their symbol. Quantities are serialized in a
+//! structured form like `{"value": 3.0, "unit": "km"}`. For deserialization
+//! of quantities both the structured form and a compact string form like
+//! `"3 km"` are accepted. In order to serialize a quantity in the compact
+//! form, the functions in module [compact] can be used, for example via
+//! `#[serde(with = "quantities::serialization::compact")]`.
+//!
+//! Deserializing a unit symbol not belonging to the respective unit type
+//! results in an error.
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{parse, Quantity, QuantityImpl, Rate, Unit};
+
+const QTY_FIELDS: &[&str] = &["value", "unit"];
+const RATE_FIELDS: &[&str] =
+    &["term_amount", "term_unit", "per_unit_multiple", "per_unit"];
+
+/// Serializes `unit` as its symbol.
+///
+/// # Errors
+///
+/// This function will only return an error returned from the serializer.
+pub fn serialize_unit<U, S>(unit: &U, serializer: S) -> Result<S::Ok, S::Error>
+where
+    U: Unit,
+    S: Serializer,
+{
+    serializer.serialize_str(unit.symbol())
+}
+
+/// Deserializes a unit of type `U` from its symbol.
+///
+/// # Errors
+///
+/// Returns an error if the deserialized value is not a string or the string
+/// is not the symbol of a variant of `U`.
+pub fn deserialize_unit<'de, U, D>(deserializer: D) -> Result<U, D::Error>
+where
+    U: Unit,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(UnitVisitor(PhantomData))
+}
+
+/// Serializes `qty` in the structured form, i.e. as struct with fields
+/// `value` and `unit`.
+///
+/// # Errors
+///
+/// This function will only return an error returned from the serializer.
+pub fn serialize_qty<Q, S>(qty: &Q, serializer: S) -> Result<S::Ok, S::Error>
+where
+    Q: Quantity,
+    Q::Value: Serialize,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("Quantity", 2)?;
+    state.serialize_field("value", &qty.value())?;
+    state.serialize_field("unit", &UnitRepr(qty.unit()))?;
+    state.end()
+}
+
+/// Deserializes a quantity of type `Q` from the structured form or - if the
+/// deserializer is human readable - from the compact string form.
+///
+/// # Errors
+///
+/// Returns an error if the deserialized value has none of the supported
+/// forms, if its numerical part can't be deserialized as `Q::Value` or if its
+/// unit symbol is not the symbol of a variant of `Q::UnitType`.
+pub fn deserialize_qty<'de, Q, D>(deserializer: D) -> Result<Q, D::Error>
+where
+    Q: QuantityImpl,
+    Q::Value: Deserialize<'de> + FromStr,
+    D: Deserializer<'de>,
+{
+    let visitor = QtyVisitor(PhantomData);
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_struct("Quantity", QTY_FIELDS, visitor)
+    }
+}
+
+/// Functions to serialize quantities in the compact string form (like
+/// `"3 km"`), usable via `#[serde(with = "...")]`.
+pub mod compact {
+    use super::{
+        de, fmt, Deserialize, Deserializer, FromStr, Quantity, QuantityImpl,
+        Serializer, Unit,
+    };
+
+    /// Serializes `qty` as string consisting of its value and the symbol of
+    /// its unit, separated by a space.
+    ///
+    /// # Errors
+    ///
+    /// This function will only return an error returned from the serializer.
+    pub fn serialize<Q, S>(qty: &Q, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        Q: Quantity,
+        S: Serializer,
+    {
+        serializer.collect_str(&format_args!(
+            "{} {}",
+            qty.value(),
+            qty.unit().symbol()
+        ))
+    }
+
+    /// Deserializes a quantity of type `Q` from the compact string form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deserialized value is not a string or the
+    /// string can't be parsed as `Q`.
+    pub fn deserialize<'de, Q, D>(deserializer: D) -> Result<Q, D::Error>
+    where
+        Q: QuantityImpl,
+        Q::Value: Deserialize<'de> + FromStr,
+        D: Deserializer<'de>,
+    {
+        struct CompactVisitor<Q>(core::marker::PhantomData<Q>);
+
+        impl<'de, Q> de::Visitor<'de> for CompactVisitor<Q>
+        where
+            Q: QuantityImpl,
+            Q::Value: FromStr,
+        {
+            type Value = Q;
+
+            fn expecting(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+                form.write_str("a quantity like \"3 km\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Q, E> {
+                super::parse_qty(v)
+            }
+        }
+
+        deserializer.deserialize_str(CompactVisitor(core::marker::PhantomData))
+    }
+}
+
+impl<TQ, PQ> Serialize for Rate<TQ, PQ>
+where
+    TQ: Quantity,
+    TQ::Value: Serialize,
+    PQ: Quantity<Value = TQ::Value>,
+{
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Rate", 4)?;
+        state.serialize_field("term_amount", &self.term_amount())?;
+        state.serialize_field("term_unit", &UnitRepr(self.term_unit()))?;
+        state.serialize_field(
+            "per_unit_multiple",
+            &self.per_unit_multiple(),
+        )?;
+        state.serialize_field("per_unit", &UnitRepr(self.per_unit()))?;
+        state.end()
+    }
+}
+
+impl<'de, TQ, PQ> Deserialize<'de> for Rate<TQ, PQ>
+where
+    TQ: Quantity,
+    TQ::Value: Deserialize<'de>,
+    PQ: Quantity<Value = TQ::Value>,
+{
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct(
+            "Rate",
+            RATE_FIELDS,
+            RateVisitor(PhantomData),
+        )
+    }
+}
+
+/// Wrapper used to (de)serialize a unit by its symbol.
+struct UnitRepr<U>(U);
+
+impl<U: Unit> Serialize for UnitRepr<U> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_unit(&self.0, serializer)
+    }
+}
+
+impl<'de, U: Unit> Deserialize<'de> for UnitRepr<U> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserialize_unit(deserializer).map(UnitRepr)
+    }
+}
+
+/// Error message for an unknown unit symbol, listing the valid symbols.
+struct UnknownUnit<'a, U>(&'a str, PhantomData<U>);
+
+impl<'a, U: Unit> fmt::Display for UnknownUnit<'a, U> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "unknown unit symbol '{}', expected one of ", self.0)?;
+        for (idx, unit) in U::iter().enumerate() {
+            if idx > 0 {
+                form.write_str(", ")?;
+            }
+            write!(form, "'{}'", unit.symbol())?;
+        }
+        Ok(())
+    }
+}
+
+fn unit_from_symbol<U: Unit, E: de::Error>(symbol: &str) -> Result<U, E> {
+    U::iter()
+        .find(|unit| parse::symbol_matches(unit.symbol(), symbol))
+        .copied()
+        .ok_or_else(|| E::custom(UnknownUnit::<U>(symbol, PhantomData)))
+}
+
+fn parse_qty<Q, E>(lit: &str) -> Result<Q, E>
+where
+    Q: QuantityImpl,
+    Q::Value: FromStr,
+    E: de::Error,
+{
+    match Q::parse(lit) {
+        Ok(qty) => Ok(qty),
+        Err(crate::ParseQuantityError::UnknownUnit) => {
+            let (_, symbol) = parse::split_qty_lit(lit).unwrap_or_default();
+            Err(E::custom(UnknownUnit::<Q::UnitType>(symbol, PhantomData)))
+        }
+        Err(err) => Err(E::custom(format_args!(
+            "invalid quantity '{}': {}",
+            lit, err
+        ))),
+    }
+}
+
+struct UnitVisitor<U>(PhantomData<U>);
+
+impl<'de, U: Unit> Visitor<'de> for UnitVisitor<U> {
+    type Value = U;
+
+    fn expecting(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        form.write_str("a unit symbol")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<U, E> {
+        unit_from_symbol(v)
+    }
+}
+
+/// Seed used to deserialize a field identifier as index into the given list
+/// of field names.
+struct FieldSeed(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldSeed {
+    type Value = usize;
+
+    fn expecting(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        form.write_str("a field identifier")
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+        match v as usize {
+            idx if idx < self.0.len() => Ok(idx),
+            _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        self.0
+            .iter()
+            .position(|name| *name == v)
+            .ok_or_else(|| E::unknown_field(v, self.0))
+    }
+}
+
+struct QtyVisitor<Q>(PhantomData<Q>);
+
+impl<'de, Q> Visitor<'de> for QtyVisitor<Q>
+where
+    Q: QuantityImpl,
+    Q::Value: Deserialize<'de> + FromStr,
+{
+    type Value = Q;
+
+    fn expecting(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        form.write_str(
+            "a quantity like {\"value\": 3, \"unit\": \"km\"} or \"3 km\"",
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Q, E> {
+        parse_qty(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Q, A::Error> {
+        let value = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let unit: UnitRepr<Q::UnitType> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Q::new(value, unit.0))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Q, A::Error> {
+        let mut value = None;
+        let mut unit: Option<UnitRepr<Q::UnitType>> = None;
+        while let Some(idx) = map.next_key_seed(FieldSeed(QTY_FIELDS))? {
+            if idx == 0 {
+                if value.is_some() {
+                    return Err(de::Error::duplicate_field(QTY_FIELDS[0]));
+                }
+                value = Some(map.next_value()?);
+            } else {
+                if unit.is_some() {
+                    return Err(de::Error::duplicate_field(QTY_FIELDS[1]));
+                }
+                unit = Some(map.next_value()?);
+            }
+        }
+        let value =
+            value.ok_or_else(|| de::Error::missing_field(QTY_FIELDS[0]))?;
+        let unit = unit.ok_or_else(|| de::Error::missing_field(QTY_FIELDS[1]))?;
+        Ok(Q::new(value, unit.0))
+    }
+}
+
+struct RateVisitor<TQ, PQ>(PhantomData<(TQ, PQ)>);
+
+impl<'de, TQ, PQ> Visitor<'de> for RateVisitor<TQ, PQ>
+where
+    TQ: Quantity,
+    TQ::Value: Deserialize<'de>,
+    PQ: Quantity<Value = TQ::Value>,
+{
+    type Value = Rate<TQ, PQ>;
+
+    fn expecting(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        form.write_str("struct Rate")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let term_amount = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let term_unit: UnitRepr<TQ::UnitType> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let per_unit_multiple = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let per_unit: UnitRepr<PQ::UnitType> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        Ok(Rate::new(term_amount, term_unit.0, per_unit_multiple, per_unit.0))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut term_amount = None;
+        let mut term_unit: Option<UnitRepr<TQ::UnitType>> = None;
+        let mut per_unit_multiple = None;
+        let mut per_unit: Option<UnitRepr<PQ::UnitType>> = None;
+        while let Some(idx) = map.next_key_seed(FieldSeed(RATE_FIELDS))? {
+            let is_dup = match idx {
+                0 => term_amount.replace(map.next_value()?).is_some(),
+                1 => term_unit.replace(map.next_value()?).is_some(),
+                2 => per_unit_multiple.replace(map.next_value()?).is_some(),
+                _ => per_unit.replace(map.next_value()?).is_some(),
+            };
+            if is_dup {
+                return Err(de::Error::duplicate_field(RATE_FIELDS[idx]));
+            }
+        }
+        let missing = |idx: usize| de::Error::missing_field(RATE_FIELDS[idx]);
+        Ok(Rate::new(
+            term_amount.ok_or_else(|| missing(0))?,
+            term_unit.ok_or_else(|| missing(1))?.0,
+            per_unit_multiple.ok_or_else(|| missing(2))?,
+            per_unit.ok_or_else(|| missing(3))?.0,
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[quantity]
+    #[ref_unit(Meter, "m", NONE)]
+    #[unit(Micrometer, "µm", MICRO, 0.000001)]
+    #[unit(Millimeter, "mm", MILLI, 0.001)]
+    #[unit(Kilometer, "km", KILO, 1000)]
+    #[unit(Foot, "ft", 0.3048)]
+    struct Length {}
+
+    #[quantity]
+    #[ref_unit(Second, "s", NONE)]
+    #[unit(Hour, "h", 3600)]
+    struct Time {}
+
+    fn err_msg<T>(json: &str) -> String
+    where
+        T: for<'de> Deserialize<'de> + fmt::Debug,
+    {
+        serde_json::from_str::<T>(json).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_unit() {
+        assert_eq!(serde_json::to_string(&KILOMETER).unwrap(), "\"km\"");
+        let u: LengthUnit = serde_json::from_str("\"mm\"").unwrap();
+        assert_eq!(u, MILLIMETER);
+        let u: LengthUnit = serde_json::from_str("\"um\"").unwrap();
+        assert_eq!(u, MICROMETER);
+        assert!(err_msg::<LengthUnit>("\"kg\"").starts_with(
+            "unknown unit symbol 'kg', expected one of 'µm', 'mm', 'ft', 'm', \
+             'km'"
+        ));
+        assert!(err_msg::<LengthUnit>("5")
+            .starts_with("invalid type: integer `5`, expected a unit symbol"));
+    }
+
+    #[test]
+    fn test_qty_round_trip() {
+        let l: Length<f64> = 3.0 * KILOMETER;
+        let json = serde_json::to_string(&l).unwrap();
+        assert_eq!(json, r#"{"value":3.0,"unit":"km"}"#);
+        assert_eq!(serde_json::from_str::<Length<f64>>(&json).unwrap(), l);
+        let l: Length = Amnt!(17.5) * METER;
+        let json = serde_json::to_string(&l).unwrap();
+        assert_eq!(serde_json::from_str::<Length>(&json).unwrap(), l);
+        let l: Length<i64> = 7_i64 * FOOT;
+        let json = serde_json::to_string(&l).unwrap();
+        assert_eq!(json, r#"{"value":7,"unit":"ft"}"#);
+        assert_eq!(serde_json::from_str::<Length<i64>>(&json).unwrap(), l);
+        let l: Length<f64> =
+            serde_json::from_str(r#"{"unit":"m","value":2.5}"#).unwrap();
+        assert_eq!(l, 2.5 * METER);
+        let l: Length<f64> = serde_json::from_str(r#"[2.5,"m"]"#).unwrap();
+        assert_eq!(l, 2.5 * METER);
+    }
+
+    #[test]
+    fn test_qty_compact() {
+        let l: Length<f64> = 3.0 * KILOMETER;
+        assert_eq!(
+            serde_json::from_str::<Length<f64>>("\"3 km\"").unwrap(),
+            l
+        );
+        assert_eq!(
+            serde_json::from_str::<Length<f64>>("\" 3km \"").unwrap(),
+            l
+        );
+        let mut buf = Vec::new();
+        compact::serialize(&l, &mut serde_json::Serializer::new(&mut buf))
+            .unwrap();
+        assert_eq!(buf, b"\"3 km\"");
+        let mut de = serde_json::Deserializer::from_str("\"2.5 km\"");
+        let l: Length<f64> = compact::deserialize(&mut de).unwrap();
+        assert_eq!(l, 2.5 * KILOMETER);
+        // the compact form does not accept the structured form
+        let mut de =
+            serde_json::Deserializer::from_str(r#"{"value":3.0,"unit":"km"}"#);
+        let err = compact::deserialize::<Length<f64>, _>(&mut de).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "invalid type: map, expected a quantity like \"3 km\""
+        ));
+    }
+
+    #[test]
+    fn test_qty_errors() {
+        assert!(err_msg::<Length<f64>>("\"3 kg\"")
+            .starts_with("unknown unit symbol 'kg'"));
+        assert!(err_msg::<Length<f64>>(r#"{"value":3.0,"unit":"xy"}"#)
+            .starts_with("unknown unit symbol 'xy'"));
+        assert!(err_msg::<Length<f64>>(r#"{"value":3.0,"unit":5}"#)
+            .starts_with("invalid type: integer `5`, expected a unit symbol"));
+        assert!(err_msg::<Length<f64>>("\"x km\"").starts_with(
+            "invalid quantity 'x km': invalid number in quantity literal"
+        ));
+        assert!(err_msg::<Length<i64>>("\"2.5 km\"")
+            .starts_with("invalid quantity '2.5 km'"));
+        assert!(err_msg::<Length<f64>>(r#"{"value":3.0}"#)
+            .starts_with("missing field `unit`"));
+        assert!(err_msg::<Length<f64>>(r#"{"value":3.0,"value":2.0}"#)
+            .starts_with("duplicate field `value`"));
+        assert!(err_msg::<Length<f64>>(r#"{"value":3.0,"size":2.0}"#)
+            .starts_with("unknown field `size`"));
+        assert!(err_msg::<Length<f64>>("3.0").starts_with(
+            "invalid type: floating point `3.0`, expected a quantity like"
+        ));
+    }
+
+    #[test]
+    fn test_rate() {
+        let r: Rate<Length<f64>, Time<f64>> =
+            Rate::new(80.0, KILOMETER, 1.0, HOUR);
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(
+            json,
+            r#"{"term_amount":80.0,"term_unit":"km","per_unit_multiple":1.0,"#
+                .to_owned()
+                + r#""per_unit":"h"}"#
+        );
+        let r2: Rate<Length<f64>, Time<f64>> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(r2.to_string(), r.to_string());
+        let r2: Rate<Length<f64>, Time<f64>> =
+            serde_json::from_str(r#"[80.0,"km",1.0,"h"]"#).unwrap();
+        assert_eq!(r2.to_string(), r.to_string());
+        assert!(err_msg::<Rate<Length<f64>, Time<f64>>>(
+            r#"{"term_amount":80.0,"term_unit":"km","per_unit":"h"}"#
+        )
+        .starts_with("missing field `per_unit_multiple`"));
+        assert!(err_msg::<Rate<Length<f64>, Time<f64>>>(
+            r#"[80.0,"h",1.0,"h"]"#
+        )
+        .starts_with("unknown unit symbol 'h'"));
+    }
+}
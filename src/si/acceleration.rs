@@ -0,0 +1,18 @@
+//! Definition of derived quantity `Acceleration` (Velocity / Time).
+
+use crate::prelude::*;
+
+use super::{time::Time, velocity::Velocity};
+
+#[quantity(Velocity / Time, dim = L / T^2)]
+#[ref_unit(
+    Meter_per_Second_squared,
+    "m/s²",
+    NONE,
+    "Reference unit of quantity `Acceleration`"
+)]
+#[unit(Millimeter_per_Second_squared, "mm/s²", MILLI, 0.001, "mm/s²")]
+#[unit(Centimeter_per_Second_squared, "cm/s²", CENTI, 0.01, "cm/s²")]
+#[unit(Kilometer_per_Second_squared, "km/s²", KILO, 1000, "km/s²")]
+/// The rate of change of the velocity of an object.
+pub struct Acceleration;
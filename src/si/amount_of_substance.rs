@@ -0,0 +1,14 @@
+//! Definition of basic quantity `AmountOfSubstance`.
+
+use crate::prelude::*;
+
+#[quantity(dim = N)]
+#[ref_unit(
+    Mole,
+    "mol",
+    NONE,
+    "Reference unit of quantity `AmountOfSubstance`",
+    prefixes = [NANO..=MILLI, KILO]
+)]
+/// The number of elementary entities in a sample, measured in moles.
+pub struct AmountOfSubstance;
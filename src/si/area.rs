@@ -0,0 +1,16 @@
+//! Definition of derived quantity `Area` (Length * Length).
+
+use crate::prelude::*;
+
+use super::length::Length;
+
+#[quantity(Length * Length, dim = L^2)]
+#[ref_unit(Square_Meter, "m²", NONE, "Reference unit of quantity `Area`")]
+#[unit(Square_Millimeter, "mm²", MICRO, 0.000001, "mm²")]
+#[unit(Square_Centimeter, "cm²", 0.0001, "cm²")]
+#[unit(Square_Decimeter, "dm²", CENTI, 0.01, "dm²")]
+#[unit(Are, "a", HECTO, 100, "100·m²")]
+#[unit(Hectare, "ha", 10000, "100·a")]
+#[unit(Square_Kilometer, "km²", MEGA, 1000000, "km²")]
+/// The two-dimensional extent of a surface.
+pub struct Area;
@@ -0,0 +1,14 @@
+//! Definition of basic quantity `ElectricCurrent`.
+
+use crate::prelude::*;
+
+#[quantity(dim = I)]
+#[ref_unit(
+    Ampere,
+    "A",
+    NONE,
+    "Reference unit of quantity `ElectricCurrent`",
+    prefixes = [NANO..=MILLI, KILO]
+)]
+/// The flow of electric charge through a conductor.
+pub struct ElectricCurrent;
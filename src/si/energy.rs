@@ -0,0 +1,19 @@
+//! Definition of derived quantity `Energy` (Force * Length).
+
+use crate::prelude::*;
+
+use super::{force::Force, length::Length};
+
+#[quantity(Force * Length, dim = M * L^2 / T^2)]
+#[ref_unit(
+    Joule,
+    "J",
+    NONE,
+    "Reference unit of quantity `Energy`",
+    prefixes = [MILLI, KILO..=GIGA]
+)]
+#[unit(Watthour, "Wh", 3600, "W·h")]
+#[unit(Kilowatthour, "kWh", 3600000, "kW·h")]
+#[unit(Megawatthour, "MWh", 3600000000., "MW·h")]
+/// The capacity of a body to perform work.
+pub struct Energy;
@@ -0,0 +1,16 @@
+//! Definition of derived quantity `Force` (Mass * Acceleration).
+
+use crate::prelude::*;
+
+use super::{acceleration::Acceleration, mass::Mass};
+
+#[quantity(Mass * Acceleration, dim = M * L / T^2)]
+#[ref_unit(
+    Newton,
+    "N",
+    NONE,
+    "Reference unit of quantity `Force`",
+    prefixes = [MICRO..=MILLI, KILO..=MEGA]
+)]
+/// The influence that causes an object to change its velocity.
+pub struct Force;
@@ -0,0 +1,62 @@
+//! Definition of derived quantity `Frequency` (1 / Time).
+
+use crate::prelude::*;
+
+use super::time::Time;
+use crate::AMNT_ONE;
+
+#[quantity(dim = T^-1)]
+#[ref_unit(
+    Hertz,
+    "Hz",
+    NONE,
+    "Reference unit of quantity `Frequency`",
+    prefixes = [KILO..=TERA]
+)]
+/// The number of occurrences of a repeating event per unit of time.
+pub struct Frequency;
+
+// Returns `value` divided by `qty`, i. e. a quantity of type `R` whose unit
+// is the reciprocal of the unit of `qty`, if there is such a unit.
+fn div_value<V, Q, R>(value: V, qty: Q) -> R
+where
+    V: QuantityValue,
+    Q: HasRefUnit<Value = V>,
+    R: HasRefUnit<Value = V>,
+    Q::UnitType: LinearScaledUnit,
+    R::UnitType: LinearScaledUnit,
+{
+    let scale = AMNT_ONE / qty.unit().scale();
+    let amnt = value / qty.value();
+    match R::unit_from_scale(scale) {
+        Some(unit) => R::new(amnt, unit),
+        None => R::_fit(amnt.mul_amount(scale)),
+    }
+}
+
+// Implements `value / Time` and `value / Frequency` for the given value types.
+macro_rules! impl_div_value_by_qty {
+    ($($t:ty),*) => {
+        $(
+        impl Div<Time<$t>> for $t {
+            type Output = Frequency<$t>;
+
+            fn div(self, rhs: Time<$t>) -> Self::Output {
+                div_value(self, rhs)
+            }
+        }
+
+        impl Div<Frequency<$t>> for $t {
+            type Output = Time<$t>;
+
+            fn div(self, rhs: Frequency<$t>) -> Self::Output {
+                div_value(self, rhs)
+            }
+        }
+        )*
+    };
+}
+
+impl_div_value_by_qty!(f32, f64, i32, i64);
+#[cfg(feature = "fpdec")]
+impl_div_value_by_qty!(Decimal);
@@ -0,0 +1,15 @@
+//! Definition of basic quantity `LuminousIntensity`.
+
+use crate::prelude::*;
+
+#[quantity(dim = J)]
+#[ref_unit(
+    Candela,
+    "cd",
+    NONE,
+    "Reference unit of quantity `LuminousIntensity`",
+    prefixes = [MILLI, KILO]
+)]
+/// The wavelength-weighted power emitted by a light source in a particular
+/// direction per unit solid angle.
+pub struct LuminousIntensity;
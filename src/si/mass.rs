@@ -1,7 +1,9 @@
+//! Definition of basic quantity `Mass`.
+
 use crate::prelude::*;
 
-#[quantity]
+#[quantity(dim = M)]
 #[ref_unit(Kilogram, "kg", KILO, "Reference unit of quantity `Mass`")]
-#[unit(Milligram, "mg", MILLI, 0.000001, "0.001·g")]
-#[unit(Gram, "g", NONE, 0.001, "0.001·kg")]
+#[unit(Gram, "g", NONE, 0.001, "0.001·kg", prefixes = [MILLI])]
+/// The quantity of matter in a physical body.
 pub struct Mass;
\ No newline at end of file
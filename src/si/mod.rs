@@ -1,23 +1,415 @@
-use self::{mass::{Mass, KILOGRAM}, velocity::{METER_PER_SECOND, Velocity}};
+//! Predefined quantities of the International System of Units (SI).
+//!
+//! Each module can be activated by the feature with the corresponding name.
 
+#[cfg(feature = "mass")]
+use self::mass::{Mass, KILOGRAM};
+#[cfg(feature = "mass")]
+use crate::Amnt;
+#[allow(unused_imports)]
+use crate::{
+    dimension::{DynDim, HasDimension},
+    parse, LinearScaledUnit, Quantity, SIPrefix, Unit, UnitExpr,
+};
+
+#[cfg(feature = "acceleration")]
+pub mod acceleration;
+#[cfg(feature = "amount_of_substance")]
+pub mod amount_of_substance;
+#[cfg(feature = "area")]
+pub mod area;
+#[cfg(feature = "electric_current")]
+pub mod electric_current;
+#[cfg(feature = "energy")]
+pub mod energy;
+#[cfg(feature = "force")]
+pub mod force;
+#[cfg(feature = "frequency")]
+pub mod frequency;
+#[cfg(feature = "length")]
 pub mod length;
+#[cfg(feature = "luminous_intensity")]
+pub mod luminous_intensity;
+#[cfg(feature = "mass")]
 pub mod mass;
+#[cfg(feature = "power")]
+pub mod power;
+#[cfg(feature = "temperature")]
 pub mod temperature;
+#[cfg(feature = "temperature_interval")]
+pub mod temperature_interval;
+#[cfg(feature = "time")]
 pub mod time;
+#[cfg(feature = "velocity")]
 pub mod velocity;
+#[cfg(feature = "volume")]
+pub mod volume;
+
+// Returns the dimension and the scale of the unit with the given symbol,
+// together with its SI prefix, looked up in the units of the enabled
+// quantities having a dimension.
+#[allow(unused_variables, clippy::missing_const_for_fn)]
+pub(crate) fn lookup_unit(
+    symbol: &str,
+) -> Option<(UnitExpr, Option<SIPrefix>)> {
+    macro_rules! lookup {
+        ($($feature:literal => $qty:ty),* $(,)?) => {
+            $(
+            #[cfg(feature = $feature)]
+            if let Some(unit) = <$qty>::iter_units()
+                .find(|unit| parse::symbol_matches(unit.symbol(), symbol))
+            {
+                let dim = DynDim::of::<<$qty as HasDimension>::Dim>();
+                return Some((
+                    UnitExpr::new(dim, unit.scale()),
+                    unit.si_prefix(),
+                ));
+            }
+            )*
+        };
+    }
+    lookup!(
+        "length" => length::Length,
+        "mass" => mass::Mass,
+        "time" => time::Time,
+        "electric_current" => electric_current::ElectricCurrent,
+        "temperature_interval" => temperature_interval::TemperatureInterval,
+        "amount_of_substance" => amount_of_substance::AmountOfSubstance,
+        "luminous_intensity" => luminous_intensity::LuminousIntensity,
+        "velocity" => velocity::Velocity,
+        "area" => area::Area,
+        "volume" => volume::Volume,
+        "acceleration" => acceleration::Acceleration,
+        "force" => force::Force,
+        "energy" => energy::Energy,
+        "power" => power::Power,
+        "frequency" => frequency::Frequency,
+    );
+    None
+}
 
-const MY_WEIGHT: Mass = 47.0 * KILOGRAM;
-const SPEED_OF_LIGHT: Velocity = 299792458.0 * METER_PER_SECOND;
+#[cfg(feature = "mass")]
+const MY_WEIGHT: Mass = KILOGRAM.mul_value(Amnt!(47.0));
 
 #[cfg(test)]
 mod tests {
-    use crate::Quantity;
+    // which of the imports are used depends on the enabled features
+    #![allow(unused_imports)]
 
-    use super::{MY_WEIGHT, mass::KILOGRAM};
+    #[cfg(feature = "alloc")]
+    use alloc::format;
 
+    use crate::{AdditiveQuantity, Amnt, HasRefUnit, Quantity};
+
+    #[cfg(feature = "mass")]
     #[test]
     fn const_quantities() {
+        use super::{
+            mass::{Mass, GRAM, KILOGRAM},
+            MY_WEIGHT,
+        };
+
+        const WEIGHT: Mass = Mass::new(Amnt!(47000.), GRAM);
+
         let _t = MY_WEIGHT.value();
-        assert_eq!(MY_WEIGHT, 47.0 * KILOGRAM);
+        assert_eq!(MY_WEIGHT, Amnt!(47.0) * KILOGRAM);
+        assert_eq!(WEIGHT, MY_WEIGHT);
+    }
+
+    #[cfg(all(feature = "mass", feature = "fpdec"))]
+    #[test]
+    fn const_quantities_fpdec() {
+        use super::mass::{Mass, GRAM, KILOGRAM};
+        use crate::{Dec, Decimal};
+
+        const WEIGHT: Mass = KILOGRAM.mul_value(Dec!(47.25));
+        const SUGAR: Mass = Mass::new(Dec!(0.5), KILOGRAM);
+
+        assert_eq!(WEIGHT.value(), Dec!(47.25));
+        assert_eq!(WEIGHT.unit(), KILOGRAM);
+        assert_eq!(SUGAR.convert(GRAM).value(), Dec!(500));
+        assert_eq!(SUGAR.convert(GRAM).unit(), GRAM);
+    }
+
+    #[cfg(feature = "velocity")]
+    #[test]
+    fn speed_of_light() {
+        use super::velocity::{
+            Velocity, KILOMETER_PER_HOUR, METER_PER_SECOND,
+        };
+
+        const SPEED_OF_LIGHT: Velocity =
+            METER_PER_SECOND.mul_value(Amnt!(299792458.0));
+        let v = SPEED_OF_LIGHT.convert(KILOMETER_PER_HOUR);
+        assert_eq!(v.unit(), KILOMETER_PER_HOUR);
+        assert_eq!(v, SPEED_OF_LIGHT);
+    }
+
+    #[cfg(all(
+        feature = "electric_current",
+        feature = "amount_of_substance",
+        feature = "luminous_intensity"
+    ))]
+    #[test]
+    fn base_quantities() {
+        use super::{
+            amount_of_substance::{KILOMOLE, MOLE},
+            electric_current::{AMPERE, MILLIAMPERE},
+            luminous_intensity::{CANDELA, MILLICANDELA},
+        };
+
+        assert_eq!(Amnt!(1500.0) * MILLIAMPERE, Amnt!(1.5) * AMPERE);
+        assert_eq!(
+            (Amnt!(0.25) * KILOMOLE).convert(MOLE),
+            Amnt!(250.0) * MOLE
+        );
+        assert!(Amnt!(20.0) * MILLICANDELA < Amnt!(0.1) * CANDELA);
+    }
+
+    #[cfg(all(feature = "length", feature = "mass"))]
+    #[test]
+    fn prefixed_units() {
+        use super::{
+            length::{Length, DECIMETER, KILOMETER, MICROMETER, NANOMETER},
+            mass::{Mass, MILLIGRAM},
+        };
+        use crate::{LinearScaledUnit, SIPrefix, Unit};
+
+        assert_eq!(MICROMETER.name(), "Micrometer");
+        assert_eq!(MICROMETER.symbol(), "µm");
+        assert_eq!(MICROMETER.si_prefix(), Some(SIPrefix::MICRO));
+        assert_eq!(MICROMETER.scale(), Amnt!(0.000001));
+        assert_eq!(KILOMETER.scale(), Amnt!(1000.0));
+        assert_eq!(<Length>::unit_from_symbol("dm"), Some(DECIMETER));
+        assert_eq!(<Length>::iter_units().next(), Some(&NANOMETER));
+        assert_eq!(MILLIGRAM.symbol(), "mg");
+        assert_eq!(MILLIGRAM.scale(), Amnt!(0.000001));
+        assert_eq!(<Mass>::unit_from_symbol("mg"), Some(MILLIGRAM));
+    }
+
+    #[cfg(feature = "length")]
+    #[test]
+    fn arith_ops() {
+        use super::length::{Length, CENTIMETER, KILOMETER, METER};
+
+        let mut len = Amnt!(2.) * METER;
+        len += Amnt!(50.) * CENTIMETER;
+        assert_eq!(len, Amnt!(2.5) * METER);
+        len -= Amnt!(1.) * METER;
+        assert_eq!(len.unit(), METER);
+        assert_eq!(len.value(), Amnt!(1.5));
+        len *= Amnt!(4.);
+        assert_eq!(len.value(), Amnt!(6.));
+        len /= Amnt!(3.);
+        assert_eq!(len.value(), Amnt!(2.));
+        assert_eq!((-len).value(), Amnt!(-2.));
+        assert_eq!((-len).unit(), METER);
+        let rem = Amnt!(7.) * METER % (Amnt!(200.) * CENTIMETER);
+        assert_eq!(rem.unit(), METER);
+        assert_eq!(rem.value(), Amnt!(1.));
+        let lengths = [
+            Amnt!(1.) * KILOMETER,
+            Amnt!(500.) * METER,
+            Amnt!(5000.) * CENTIMETER,
+        ];
+        let total: Length = lengths.iter().sum();
+        assert_eq!(total.unit(), KILOMETER);
+        assert_eq!(total, Amnt!(1550.) * METER);
+        let total: Length = lengths.into_iter().rev().sum();
+        assert_eq!(total.unit(), CENTIMETER);
+        assert_eq!(total.value(), Amnt!(155000.));
+        let total: Length = core::iter::empty::<Length>().sum();
+        assert_eq!(total.unit(), METER);
+        assert_eq!(total.value(), Amnt!(0.0));
+        let total = <Length>::sum_in(lengths, METER);
+        assert_eq!(total.unit(), METER);
+        assert_eq!(total.value(), Amnt!(1550.));
+        let total = <Length>::sum_in(None, KILOMETER);
+        assert_eq!(total.unit(), KILOMETER);
+        assert_eq!(total.value(), Amnt!(0.0));
+    }
+
+    #[cfg(all(feature = "length", feature = "alloc"))]
+    #[test]
+    fn display() {
+        use super::length::{CENTIMETER, METER};
+
+        let len = Amnt!(12.5) * METER;
+        assert_eq!(format!("{len:.1}"), "12.5 m");
+        assert_eq!(format!("{:+010.2}", len), "+0012.50 m");
+        assert_eq!(format!("{:^11.1}", -len), "  -12.5 m  ");
+        assert_eq!(format!("{len:*<9.1}"), "12.5 m***");
+        assert_eq!(format!("{len:>#14.1}"), "   12.5 Meters");
+        let len = Amnt!(1.) * CENTIMETER;
+        assert_eq!(format!("{len:#.0}"), "1 Centimeter");
+        assert_eq!(format!("{len:#.1}"), "1.0 Centimeters");
+        assert_eq!(format!("{:#.0}", -len), "-1 Centimeter");
+    }
+
+    #[cfg(feature = "length")]
+    #[test]
+    fn generic_fns() {
+        use super::length::{CENTIMETER, METER};
+
+        fn sum_and_max<Q: AdditiveQuantity>(a: Q, b: Q) -> (Q, Q) {
+            (a + b, if a < b { b } else { a })
+        }
+
+        let (sum, max) =
+            sum_and_max(Amnt!(2.) * METER, Amnt!(250.) * CENTIMETER);
+        assert_eq!(sum.unit(), METER);
+        assert_eq!(sum.value(), Amnt!(4.5));
+        assert_eq!(max.unit(), CENTIMETER);
+        let d = (Amnt!(2.) * METER).div_qty(Amnt!(50.) * CENTIMETER);
+        assert_eq!(d, Amnt!(4.));
+        let s = (Amnt!(2.) * METER).add_qty(Amnt!(50.) * CENTIMETER);
+        assert_eq!(s, Amnt!(2.5) * METER);
+    }
+
+    // Asserts that `q` and `r` have the same unit and approximately the same
+    // value (conversions involving a scale like 5/9 can't be exact).
+    #[cfg(feature = "temperature")]
+    fn assert_approx_eq<Q>(q: Q, r: Q)
+    where
+        Q: HasRefUnit<Value = crate::Amount> + core::fmt::Display,
+        Q::UnitType: crate::LinearScaledUnit,
+    {
+        let diff = q.value() - r.equiv_amount(q.unit());
+        let eps = Amnt!(0.000000001);
+        assert!(-eps < diff && diff < eps, "{} != {}", q, r);
+        assert!(q.unit() == r.unit(), "{} != {}", q.unit(), r.unit());
+    }
+
+    #[cfg(feature = "temperature")]
+    #[test]
+    fn temperature() {
+        use super::temperature::{
+            Temperature, DEGREE_CELSIUS, DEGREE_FAHRENHEIT, DEGREE_RANKINE,
+            KELVIN,
+        };
+
+        let t = Amnt!(20.0) * DEGREE_CELSIUS;
+        assert_eq!(t.convert(KELVIN), Amnt!(293.15) * KELVIN);
+        assert_approx_eq(
+            t.convert(DEGREE_FAHRENHEIT),
+            Amnt!(68.0) * DEGREE_FAHRENHEIT,
+        );
+        assert_approx_eq(
+            t.convert(DEGREE_RANKINE),
+            Amnt!(527.67) * DEGREE_RANKINE,
+        );
+        assert_approx_eq(
+            (Amnt!(-40.0) * DEGREE_FAHRENHEIT).convert(DEGREE_CELSIUS),
+            Amnt!(-40.0) * DEGREE_CELSIUS,
+        );
+        assert_approx_eq(
+            (Amnt!(0.0) * KELVIN).convert(DEGREE_FAHRENHEIT),
+            Amnt!(-459.67) * DEGREE_FAHRENHEIT,
+        );
+        assert!(Amnt!(0.0) * DEGREE_CELSIUS < Amnt!(33.0) * DEGREE_FAHRENHEIT);
+        assert!(Amnt!(0.0) * DEGREE_CELSIUS > Amnt!(31.0) * DEGREE_FAHRENHEIT);
+        let t: Temperature = "300 K".parse().unwrap();
+        assert_approx_eq(
+            t.convert(DEGREE_CELSIUS),
+            Amnt!(26.85) * DEGREE_CELSIUS,
+        );
+    }
+
+    #[cfg(feature = "volume")]
+    #[test]
+    fn area_and_volume() {
+        use super::{
+            area::{Area, HECTARE, SQUARE_KILOMETER, SQUARE_METER},
+            length::{Length, KILOMETER, METER},
+            volume::{CUBIC_METER, LITER},
+        };
+
+        let a: Area = (Amnt!(2.0) * KILOMETER) * (Amnt!(3.0) * KILOMETER);
+        assert_eq!(a.unit(), SQUARE_KILOMETER);
+        assert_eq!(a, Amnt!(600.0) * HECTARE);
+        let l: Length = a / (Amnt!(3000.0) * METER);
+        assert_eq!(l, Amnt!(2.0) * KILOMETER);
+        let v = (Amnt!(2.0) * SQUARE_METER) * (Amnt!(0.5) * METER);
+        assert_eq!(v, Amnt!(1.0) * CUBIC_METER);
+        assert_eq!(v.convert(LITER).value(), Amnt!(1000.0));
+        assert_eq!(v / (Amnt!(0.5) * METER), Amnt!(2.0) * SQUARE_METER);
+    }
+
+    #[cfg(feature = "power")]
+    #[test]
+    fn mechanics() {
+        use super::{
+            acceleration::METER_PER_SECOND_SQUARED,
+            energy::{Energy, JOULE, KILOJOULE, KILOWATTHOUR},
+            force::{Force, KILONEWTON, NEWTON},
+            length::{KILOMETER, METER},
+            mass::KILOGRAM,
+            power::{Power, KILOWATT, WATT},
+            time::{Time, HOUR, SECOND},
+            velocity::METER_PER_SECOND,
+        };
+
+        let a = (Amnt!(20.0) * METER_PER_SECOND) / (Amnt!(4.0) * SECOND);
+        assert_eq!(a, Amnt!(5.0) * METER_PER_SECOND_SQUARED);
+        let f: Force = (Amnt!(3.0) * KILOGRAM) * a;
+        assert_eq!(f, Amnt!(15.0) * NEWTON);
+        let e: Energy = (Amnt!(2.0) * KILONEWTON) * (Amnt!(3.0) * METER);
+        assert_eq!(e, Amnt!(6.0) * KILOJOULE);
+        assert_eq!(
+            (Amnt!(2.0) * METER) * (Amnt!(2.5) * NEWTON),
+            Amnt!(5.0) * JOULE
+        );
+        assert_eq!(e / (Amnt!(3.0) * METER), Amnt!(2.0) * KILONEWTON);
+        let p: Power = (Amnt!(6.0) * KILOJOULE) / (Amnt!(2.0) * SECOND);
+        assert_eq!(p, Amnt!(3.0) * KILOWATT);
+        let e: Energy = (Amnt!(500.0) * WATT) * (Amnt!(2.0) * HOUR);
+        assert_eq!(e, Amnt!(1.0) * KILOWATTHOUR);
+        let t: Time = (Amnt!(1.0) * KILOWATTHOUR) / (Amnt!(1.0) * KILOWATT);
+        assert_eq!(t, Amnt!(1.0) * HOUR);
+        assert_eq!(
+            (Amnt!(1.0) * NEWTON) * (Amnt!(1.0) * KILOMETER),
+            Amnt!(1.0) * KILOJOULE
+        );
+    }
+
+    #[cfg(feature = "frequency")]
+    #[test]
+    fn frequency() {
+        use super::{
+            frequency::{Frequency, HERTZ, KILOHERTZ},
+            time::{Time, MILLISECOND, SECOND},
+        };
+
+        let f: Frequency = Amnt!(5.0) / (Amnt!(2.0) * MILLISECOND);
+        assert_eq!(f.unit(), KILOHERTZ);
+        assert_eq!(f, Amnt!(2500.0) * HERTZ);
+        let t: Time = Amnt!(10.0) / (Amnt!(4.0) * HERTZ);
+        assert_eq!(t, Amnt!(2.5) * SECOND);
+        assert_eq!(f * (Amnt!(2.0) * SECOND), Amnt!(5000.0));
+        assert_eq!((Amnt!(2.0) * SECOND) * f, Amnt!(5000.0));
+        let f: Frequency<f32> = 1.0_f32 / (0.5_f32 * SECOND);
+        assert_eq!(f, 2.0_f32 * HERTZ);
+        let t: Time<i64> = 120_i64 / (2_i64 * HERTZ);
+        assert_eq!(t, 60_i64 * SECOND);
+    }
+
+    #[cfg(all(feature = "length", feature = "area"))]
+    #[test]
+    fn value_types() {
+        use super::{
+            area::{Area, SQUARE_METER},
+            length::{Length, KILOMETER, METER},
+        };
+
+        let a: Length<f32> = 3.5_f32 * METER;
+        assert_eq!(a.value(), 3.5_f32);
+        assert_eq!(a.convert(KILOMETER).value(), 0.0035_f32);
+        let b: Length<i64> = 1500_i64 * METER;
+        let c = b.convert(KILOMETER);
+        assert_eq!(c.value(), 2_i64);
+        assert_eq!(c.unit(), KILOMETER);
+        let d: Area<i64> = (3_i64 * METER) * (2_i64 * KILOMETER);
+        assert_eq!(d.convert(SQUARE_METER).value(), 6000_i64);
+        assert_eq!(b / (3_i64 * METER), 500_i64);
     }
 }
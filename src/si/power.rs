@@ -0,0 +1,16 @@
+//! Definition of derived quantity `Power` (Energy / Time).
+
+use crate::prelude::*;
+
+use super::{energy::Energy, time::Time};
+
+#[quantity(Energy / Time, dim = M * L^2 / T^3)]
+#[ref_unit(
+    Watt,
+    "W",
+    NONE,
+    "Reference unit of quantity `Power`",
+    prefixes = [MICRO..=MILLI, KILO..=GIGA]
+)]
+/// The amount of energy transferred or converted per unit of time.
+pub struct Power;
@@ -1,18 +1,22 @@
-use crate::{prelude::*, ConversionTable};
+//! Definition of basic quantity `Temperature`.
 
-#[quantity]
-#[unit(Kelvin, "K", "K")]
-#[unit(Degree_Celsius, "°C", "°C")]
-pub struct Temperature;
+use super::temperature_interval::TemperatureInterval;
+use crate::prelude::*;
 
-/// Temperature conversion table
-/// 
-/// taken from https://github.com/mamrhein/quantities.rs/blob/main/src/temperature.rs#L39-L59
-/// edited by Luca Lewin on 02/12/2022
-pub const TEMPERATURE_CONVERTER: ConversionTable<Temperature, 2> =
-    ConversionTable {
-        mappings: [
-            (KELVIN, DEGREE_CELSIUS, 1.0, -273.15),
-            (DEGREE_CELSIUS, KELVIN, 1.0, 273.15),
-        ],
-    };
+#[quantity(interval = TemperatureInterval)]
+#[ref_unit(Kelvin, "K", NONE, "Reference unit of quantity `Temperature`")]
+#[unit(Degree_Celsius, "°C", 1.0, 273.15, "K - 273.15")]
+#[unit(Degree_Rankine, "°R", 0.5555555555555556, "5/9·K")]
+#[unit(
+    Degree_Fahrenheit,
+    "°F",
+    0.5555555555555556,
+    255.3722222222222,
+    "°R - 459.67"
+)]
+/// The thermodynamic temperature of a body.
+///
+/// Instances of `Temperature` denote points on a temperature scale. The
+/// difference of two instances is a `TemperatureInterval`, which can be added
+/// to or subtracted from a `Temperature`.
+pub struct Temperature;
@@ -0,0 +1,16 @@
+//! Definition of basic quantity `TemperatureInterval`.
+
+use crate::prelude::*;
+
+#[quantity(dim = Th)]
+#[ref_unit(
+    Kelvin,
+    "K",
+    NONE,
+    "Reference unit of quantity `TemperatureInterval`"
+)]
+#[unit(Degree_Celsius, "°C", 1, "K")]
+#[unit(Degree_Rankine, "°R", 0.5555555555555556, "5/9·K")]
+#[unit(Degree_Fahrenheit, "°F", 0.5555555555555556, "°R")]
+/// The difference between two thermodynamic temperatures.
+pub struct TemperatureInterval;
@@ -1,11 +1,17 @@
+//! Definition of basic quantity `Time`.
+
 use crate::prelude::*;
 
-#[quantity]
-#[ref_unit(Second, "s", NONE, "Reference unit of quantity `Time`")]
-#[unit(Nanosecond, "ns", NANO, 0.000000001, "0.000000001·s")]
-#[unit(Microsecond, "µs", MICRO, 0.000001, "0.000001·s")]
-#[unit(Millisecond, "ms", MILLI, 0.001, "0.001·s")]
+#[quantity(dim = T)]
+#[ref_unit(
+    Second,
+    "s",
+    NONE,
+    "Reference unit of quantity `Time`",
+    prefixes = [NANO..=MILLI]
+)]
 #[unit(Minute, "min", 60, "60·s")]
 #[unit(Hour, "h", 3600, "60·min")]
 #[unit(Day, "d", 86400, "24·h")]
+/// The duration of an event or the interval between two events.
 pub struct Time;
\ No newline at end of file
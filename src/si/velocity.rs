@@ -1,9 +1,12 @@
+//! Definition of derived quantity `Velocity` (Length / Time).
+
 use crate::prelude::*;
 
 use super::{length::Length, time::Time};
 
 
-#[quantity(Length / Time)]
+#[quantity(Length / Time, dim = L / T)]
 #[ref_unit(Meter_per_Second, "m/s", NONE, "Reference unit of quantity `Velocity`")]
 #[unit(Kilometer_per_Hour, "km/h", 0.2777777777777778, "km/h")]
+/// The rate of change of the position of an object.
 pub struct Velocity;
\ No newline at end of file
@@ -0,0 +1,20 @@
+//! Definition of derived quantity `Volume` (Area * Length, Length^3).
+
+use crate::prelude::*;
+
+use super::{area::Area, length::Length};
+
+#[quantity(Area * Length, Length^3, dim = L^3)]
+#[ref_unit(Cubic_Meter, "m³", NONE, "Reference unit of quantity `Volume`")]
+#[unit(Cubic_Millimeter, "mm³", NANO, 0.000000001, "mm³")]
+#[unit(Microliter, "µl", NANO, 0.000000001, "0.000001·l")]
+#[unit(Cubic_Centimeter, "cm³", MICRO, 0.000001, "cm³")]
+#[unit(Milliliter, "ml", MICRO, 0.000001, "0.001·l")]
+#[unit(Centiliter, "cl", 0.00001, "0.01·l")]
+#[unit(Deciliter, "dl", 0.0001, "0.1·l")]
+#[unit(Cubic_Decimeter, "dm³", MILLI, 0.001, "dm³")]
+#[unit(Liter, "l", MILLI, 0.001, "dm³")]
+#[unit(Hectoliter, "hl", DECI, 0.1, "100·l")]
+#[unit(Cubic_Kilometer, "km³", GIGA, 1000000000, "km³")]
+/// The three-dimensional extent of an object.
+pub struct Volume;
@@ -0,0 +1,606 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2022 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+    dimension::{DynDim, HasDimension},
+    parse, si, Amnt, Amount, HasRefUnit, LinearScaledUnit, SIPrefix, Unit,
+    AMNT_ONE, AMNT_ZERO,
+};
+
+/// An error which can be returned when parsing a unit expression.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseUnitError {
+    /// The given expression is empty or malformed, or its scale is out of the
+    /// range of `Amount`.
+    InvalidSyntax,
+    /// The expression contains a symbol which does not denote a known unit,
+    /// or it does not resolve to a unit of the requested quantity.
+    UnknownUnit,
+    /// The dimension of the expression differs from the dimension of the
+    /// requested quantity.
+    DimensionMismatch,
+}
+
+impl fmt::Display for ParseUnitError {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::InvalidSyntax => "invalid unit expression",
+            Self::UnknownUnit => "unknown unit in unit expression",
+            Self::DimensionMismatch => "dimension of unit expression differs",
+        };
+        fmt::Display::fmt(msg, form)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseUnitError {}
+
+/// The dimension and scale of a unit given as expression, like "kg·m/s²".
+///
+/// A unit expression is a product or quotient of unit symbols, optionally
+/// raised to an integral power, given either after a '^' (like "s^-2") or as
+/// superscript (like "s⁻²"). Factors are separated by '·', '⋅', '*', '.' or
+/// whitespace, divisors are preceded by '/', and sub-expressions can be
+/// grouped by parentheses. The unit symbols are looked up in the units of the
+/// enabled SI quantities having a dimension. Symbols of unprefixed SI units
+/// can be preceded by a SI prefix, like in "Mm" or "µN".
+///
+/// The scale of the expression is the factor to be applied to a value given
+/// in the unit expressed in order to get the value in terms of the reference
+/// unit of the quantity having the dimension of the expression.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "force")]
+/// # {
+/// use quantities::{dimension::DynDim, Amnt, UnitExpr};
+///
+/// let expr: UnitExpr = "kg·m/s²".parse().unwrap();
+/// assert_eq!(expr.dim(), DynDim([1, 1, -2, 0, 0, 0, 0]));
+/// assert_eq!(expr.scale(), Amnt!(1.0));
+/// assert_eq!(expr, "N".parse().unwrap());
+/// assert_eq!(expr, "m kg s^-2".parse().unwrap());
+/// let expr: UnitExpr = "km/ms".parse().unwrap();
+/// assert_eq!(expr, "Mm·s⁻¹".parse().unwrap());
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UnitExpr {
+    dim: DynDim,
+    scale: Amount,
+}
+
+impl UnitExpr {
+    /// Returns a new instance of `UnitExpr`.
+    #[must_use]
+    pub const fn new(dim: DynDim, scale: Amount) -> Self {
+        Self { dim, scale }
+    }
+
+    /// Returns the dimension of `self`.
+    #[must_use]
+    pub const fn dim(&self) -> DynDim {
+        self.dim
+    }
+
+    /// Returns the scale of `self` in terms of the reference unit of the
+    /// quantity having the dimension of `self`.
+    #[must_use]
+    #[allow(clippy::double_must_use)]
+    pub const fn scale(&self) -> Amount {
+        self.scale
+    }
+}
+
+impl FromStr for UnitExpr {
+    type Err = ParseUnitError;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        parse_unit_expr(expr, si::lookup_unit)
+    }
+}
+
+// Returns the unit of `Q` given by the unit expression `expr`, looking up the
+// symbols in `expr` in the units of `Q` and - if not found there - in the
+// units of the enabled SI quantities.
+pub(crate) fn unit_from_expr<Q>(
+    expr: &str,
+) -> Result<Q::UnitType, ParseUnitError>
+where
+    Q: HasRefUnit + HasDimension,
+    Q::UnitType: LinearScaledUnit,
+{
+    if let Some(unit) = Q::iter_units()
+        .find(|unit| parse::symbol_matches(unit.symbol(), expr.trim()))
+    {
+        return Ok(*unit);
+    }
+    let dim = DynDim::of::<Q::Dim>();
+    let lookup = |symbol: &str| {
+        Q::iter_units()
+            .find(|unit| parse::symbol_matches(unit.symbol(), symbol))
+            .map(|unit| {
+                (UnitExpr::new(dim, unit.scale()), unit.si_prefix())
+            })
+            .or_else(|| si::lookup_unit(symbol))
+    };
+    let res = parse_unit_expr(expr, lookup)?;
+    if res.dim != dim {
+        return Err(ParseUnitError::DimensionMismatch);
+    }
+    // the scale of the expression may differ from the scale of the unit in
+    // the last digits, due to rounding
+    Q::iter_units()
+        .find(|unit| {
+            unit.offset() == AMNT_ZERO && approx_eq(unit.scale(), res.scale)
+        })
+        .copied()
+        .ok_or(ParseUnitError::UnknownUnit)
+}
+
+// Returns `true` if `x` and `y` differ by less than 10⁻¹² relative to `x`.
+fn approx_eq(x: Amount, y: Amount) -> bool {
+    let diff = if x > y { x - y } else { y - x };
+    diff <= x * Amnt!(0.000000000001)
+}
+
+// Returns `x` * `y`, or `None` if the result is out of the range of
+// `Amount`.
+#[cfg(feature = "fpdec")]
+fn checked_mul(x: Amount, y: Amount) -> Option<Amount> {
+    fpdec::CheckedMul::checked_mul(x, y)
+}
+
+#[cfg(not(feature = "fpdec"))]
+fn checked_mul(x: Amount, y: Amount) -> Option<Amount> {
+    let res = x * y;
+    res.is_finite().then_some(res)
+}
+
+// Returns `x` / `y`, or `None` if `y` is zero or the result is out of the
+// range of `Amount`.
+#[cfg(feature = "fpdec")]
+fn checked_div(x: Amount, y: Amount) -> Option<Amount> {
+    fpdec::CheckedDiv::checked_div(x, y)
+}
+
+#[cfg(not(feature = "fpdec"))]
+fn checked_div(x: Amount, y: Amount) -> Option<Amount> {
+    let res = x / y;
+    res.is_finite().then_some(res)
+}
+
+// Returns 10ⁿ, or `None` if it is out of the range of `Amount`.
+fn pow10(exp: i8) -> Option<Amount> {
+    let ten = Amnt!(10);
+    let mut res = AMNT_ONE;
+    for _ in 0..exp.unsigned_abs() {
+        res = checked_mul(res, ten)?;
+    }
+    if exp < 0 {
+        checked_div(AMNT_ONE, res)
+    } else {
+        Some(res)
+    }
+}
+
+// Intermediate result while parsing a unit expression, holding the scale as
+// fraction in order to reduce rounding errors.
+#[derive(Copy, Clone)]
+struct Term {
+    dim: DynDim,
+    num: Amount,
+    den: Amount,
+}
+
+impl Term {
+    const ONE: Self = Self {
+        dim: DynDim::ONE,
+        num: AMNT_ONE,
+        den: AMNT_ONE,
+    };
+
+    fn mul(self, rhs: Self) -> Result<Self, ParseUnitError> {
+        Ok(Self {
+            dim: self
+                .dim
+                .checked_mul(rhs.dim)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+            num: checked_mul(self.num, rhs.num)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+            den: checked_mul(self.den, rhs.den)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+        })
+    }
+
+    fn div(self, rhs: Self) -> Result<Self, ParseUnitError> {
+        Ok(Self {
+            dim: self
+                .dim
+                .checked_div(rhs.dim)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+            num: checked_mul(self.num, rhs.den)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+            den: checked_mul(self.den, rhs.num)
+                .ok_or(ParseUnitError::InvalidSyntax)?,
+        })
+    }
+
+    fn pow(self, exp: i8) -> Result<Self, ParseUnitError> {
+        let mut res = Self::ONE;
+        for _ in 0..exp.unsigned_abs() {
+            res = res.mul(self)?;
+        }
+        if exp < 0 {
+            Self::ONE.div(res)
+        } else {
+            Ok(res)
+        }
+    }
+}
+
+const SUPERSCRIPT_DIGITS: [char; 10] =
+    ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+const fn is_operator(chr: char) -> bool {
+    matches!(
+        chr,
+        '·' | '⋅' | '*' | '.' | '/' | '^' | '(' | ')' | '⁻' | '⁺'
+    )
+}
+
+fn is_symbol_char(chr: char) -> bool {
+    !(chr.is_whitespace()
+        || chr.is_ascii_digit()
+        || is_operator(chr)
+        || SUPERSCRIPT_DIGITS.contains(&chr))
+}
+
+struct Parser<'a, F> {
+    input: &'a str,
+    lookup: F,
+}
+
+impl<'a, F> Parser<'a, F>
+where
+    F: Fn(&str) -> Option<(UnitExpr, Option<SIPrefix>)>,
+{
+    fn peek(&mut self) -> Option<char> {
+        self.input = self.input.trim_start();
+        self.input.chars().next()
+    }
+
+    fn bump(&mut self) {
+        let mut chars = self.input.chars();
+        chars.next();
+        self.input = chars.as_str();
+    }
+
+    // expr := term (('·' | '*' | '.' | ' ')? term | '/' term)*
+    fn parse_expr(&mut self) -> Result<Term, ParseUnitError> {
+        let mut res = self.parse_term()?;
+        loop {
+            match self.peek() {
+                None | Some(')') => return Ok(res),
+                Some('/') => {
+                    self.bump();
+                    res = res.div(self.parse_term()?)?;
+                }
+                Some('·' | '⋅' | '*' | '.') => {
+                    self.bump();
+                    res = res.mul(self.parse_term()?)?;
+                }
+                Some(_) => res = res.mul(self.parse_term()?)?,
+            }
+        }
+    }
+
+    // term := factor ('^' ['-' | '+'] digits | ['⁻' | '⁺'] superscripts)?
+    fn parse_term(&mut self) -> Result<Term, ParseUnitError> {
+        let factor = self.parse_factor()?;
+        let exp = if let Some(rest) = self.input.strip_prefix('^') {
+            self.input = rest;
+            self.parse_exp('-', '+', |chr| chr.to_digit(10))?
+        } else if self.input.starts_with(|chr| {
+            matches!(chr, '⁻' | '⁺') || SUPERSCRIPT_DIGITS.contains(&chr)
+        }) {
+            self.parse_exp('⁻', '⁺', |chr| {
+                SUPERSCRIPT_DIGITS
+                    .iter()
+                    .position(|sup| *sup == chr)
+                    .and_then(|digit| u32::try_from(digit).ok())
+            })?
+        } else {
+            return Ok(factor);
+        };
+        factor.pow(exp)
+    }
+
+    // Parses an optionally signed integer, using `digit` to get the value of
+    // its digits.
+    fn parse_exp(
+        &mut self,
+        minus: char,
+        plus: char,
+        digit: impl Fn(char) -> Option<u32>,
+    ) -> Result<i8, ParseUnitError> {
+        let mut neg = false;
+        if let Some(rest) = self.input.strip_prefix(minus) {
+            neg = true;
+            self.input = rest;
+        } else if let Some(rest) = self.input.strip_prefix(plus) {
+            self.input = rest;
+        }
+        let end = self
+            .input
+            .find(|chr| digit(chr).is_none())
+            .unwrap_or(self.input.len());
+        let (digits, rest) = self.input.split_at(end);
+        if digits.is_empty() {
+            return Err(ParseUnitError::InvalidSyntax);
+        }
+        self.input = rest;
+        let mut exp = 0_i8;
+        for chr in digits.chars() {
+            exp = digit(chr)
+                .and_then(|digit| i8::try_from(digit).ok())
+                .and_then(|digit| exp.checked_mul(10)?.checked_add(digit))
+                .ok_or(ParseUnitError::InvalidSyntax)?;
+        }
+        Ok(if neg { -exp } else { exp })
+    }
+
+    // factor := '(' expr ')' | '1' | symbol
+    fn parse_factor(&mut self) -> Result<Term, ParseUnitError> {
+        match self.peek() {
+            Some('(') => {
+                self.bump();
+                let res = self.parse_expr()?;
+                if self.peek() != Some(')') {
+                    return Err(ParseUnitError::InvalidSyntax);
+                }
+                self.bump();
+                Ok(res)
+            }
+            Some('1') => {
+                self.bump();
+                Ok(Term::ONE)
+            }
+            Some(chr) if is_symbol_char(chr) => {
+                let end = self
+                    .input
+                    .find(|chr| !is_symbol_char(chr))
+                    .unwrap_or(self.input.len());
+                let (symbol, rest) = self.input.split_at(end);
+                self.input = rest;
+                let expr = self
+                    .lookup_symbol(symbol)
+                    .ok_or(ParseUnitError::UnknownUnit)?;
+                Ok(Term {
+                    dim: expr.dim,
+                    num: expr.scale,
+                    den: AMNT_ONE,
+                })
+            }
+            _ => Err(ParseUnitError::InvalidSyntax),
+        }
+    }
+
+    // Looks up `symbol`, which may consist of a SI prefix followed by the
+    // symbol of an unprefixed SI unit.
+    fn lookup_symbol(&self, symbol: &str) -> Option<UnitExpr> {
+        if let Some((expr, _)) = (self.lookup)(symbol) {
+            return Some(expr);
+        }
+        symbol.char_indices().skip(1).find_map(|(idx, _)| {
+            let (abbr, rest) = symbol.split_at(idx);
+            let prefix = SIPrefix::from_abbr(abbr).or_else(|| {
+                matches!(abbr, "u" | "\u{3bc}").then_some(SIPrefix::MICRO)
+            })?;
+            match (self.lookup)(rest) {
+                Some((expr, Some(SIPrefix::NONE)))
+                    if prefix != SIPrefix::NONE =>
+                {
+                    let scale = checked_mul(expr.scale, pow10(prefix.exp())?)?;
+                    // with fixed-point decimals small scales may be rounded
+                    // to zero
+                    (scale != AMNT_ZERO)
+                        .then_some(UnitExpr::new(expr.dim, scale))
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+// Parses `expr`, looking up the unit symbols by calling `lookup`, which must
+// return the dimension and scale of the unit with the given symbol and its
+// SI prefix, if it is a SI unit.
+fn parse_unit_expr<F>(
+    expr: &str,
+    lookup: F,
+) -> Result<UnitExpr, ParseUnitError>
+where
+    F: Fn(&str) -> Option<(UnitExpr, Option<SIPrefix>)>,
+{
+    let mut parser = Parser {
+        input: expr,
+        lookup,
+    };
+    if parser.peek().is_none() {
+        return Err(ParseUnitError::InvalidSyntax);
+    }
+    let res = parser.parse_expr()?;
+    if parser.peek().is_some() {
+        // unbalanced ')'
+        return Err(ParseUnitError::InvalidSyntax);
+    }
+    let scale =
+        checked_div(res.num, res.den).ok_or(ParseUnitError::InvalidSyntax)?;
+    Ok(UnitExpr::new(res.dim, scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const L: DynDim = DynDim([1, 0, 0, 0, 0, 0, 0]);
+    const M: DynDim = DynDim([0, 1, 0, 0, 0, 0, 0]);
+
+    // "a" is an unprefixed SI unit of dimension L, "b" a non-SI unit of
+    // dimension M with scale 2
+    fn lookup(symbol: &str) -> Option<(UnitExpr, Option<SIPrefix>)> {
+        match symbol {
+            "a" => Some((UnitExpr::new(L, AMNT_ONE), Some(SIPrefix::NONE))),
+            "b" => Some((UnitExpr::new(M, Amnt!(2.0)), None)),
+            _ => None,
+        }
+    }
+
+    fn parse(expr: &str) -> Result<UnitExpr, ParseUnitError> {
+        parse_unit_expr(expr, lookup)
+    }
+
+    #[test]
+    fn test_empty() {
+        for expr in ["", "   ", "()", "a·", "a/"] {
+            assert_eq!(
+                parse(expr),
+                Err(ParseUnitError::InvalidSyntax),
+                "{expr}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parentheses() {
+        assert_eq!(parse("((a))"), parse("a"));
+        assert_eq!(parse("(a·b)/(a)"), parse("b"));
+        for expr in ["(a", "a)", "((a)", "a/(b", "(a))", ")a("] {
+            assert_eq!(
+                parse(expr),
+                Err(ParseUnitError::InvalidSyntax),
+                "{expr}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_exponent() {
+        let one = UnitExpr::new(DynDim::ONE, AMNT_ONE);
+        assert_eq!(parse("a^0"), Ok(one));
+        assert_eq!(parse("b⁰"), Ok(one));
+        assert_eq!(parse("(a·b)^-0"), Ok(one));
+        assert_eq!(parse("a^0·b"), parse("b"));
+    }
+
+    #[test]
+    fn test_negative_exponents() {
+        let expr = parse("b^-2").unwrap();
+        assert_eq!(expr.dim(), DynDim([0, -2, 0, 0, 0, 0, 0]));
+        assert_eq!(expr.scale(), Amnt!(0.25));
+        assert_eq!(parse("b⁻²"), Ok(expr));
+        assert_eq!(parse("1/b^2"), Ok(expr));
+        assert_eq!(parse("1/b^+2"), Ok(expr));
+        assert_eq!(parse("(a/b)^-1"), parse("b/a"));
+        assert_eq!(parse("a^-1^-1"), Err(ParseUnitError::InvalidSyntax));
+        assert_eq!(parse("a^-"), Err(ParseUnitError::InvalidSyntax));
+        assert_eq!(parse("a^-128"), Err(ParseUnitError::InvalidSyntax));
+    }
+
+    #[test]
+    fn test_scale_out_of_range() {
+        // 10³¹² exceeds the range of all variants of `Amount`
+        let err = Err(ParseUnitError::InvalidSyntax);
+        assert_eq!(parse("Ya^13"), err);
+        assert_eq!(parse("1/Ya^13"), err);
+        assert_eq!(parse("(Ya^10)^10"), err);
+        assert_eq!(parse("Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya·Ya"), err);
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(parse("ka"), Ok(UnitExpr::new(L, Amnt!(1000.0))));
+        assert_eq!(parse("kb"), Err(ParseUnitError::UnknownUnit));
+        assert_eq!(parse("kka"), Err(ParseUnitError::UnknownUnit));
+        assert_eq!(parse("c"), Err(ParseUnitError::UnknownUnit));
+    }
+
+    #[cfg(all(feature = "force", feature = "velocity"))]
+    #[test]
+    fn test_si_units() {
+        use crate::si::{
+            acceleration::{Acceleration, METER_PER_SECOND_SQUARED},
+            force::{Force, MICRONEWTON, NEWTON},
+            length::Length,
+            velocity::{Velocity, KILOMETER_PER_HOUR, METER_PER_SECOND},
+        };
+
+        let newton: UnitExpr = "N".parse().unwrap();
+        for expr in [
+            "kg·m/s²",
+            "kg⋅m⋅s⁻²",
+            "kg*m*s^-2",
+            "kg.m/s^2",
+            "m kg s^-2",
+            " kg m / s^+2 ",
+            "kg/(s^2/m)",
+            "(kg·m)/(s·s)",
+            "kg·m·(1/s)²",
+            "g·km/s²",
+        ] {
+            assert_eq!(expr.parse::<UnitExpr>(), Ok(newton), "{expr}");
+        }
+        let expr: UnitExpr = "Mm/ks".parse().unwrap();
+        assert_eq!(expr.dim(), DynDim([1, 0, -1, 0, 0, 0, 0]));
+        assert_eq!(expr.scale(), Amnt!(1000.0));
+        let expr: UnitExpr = "us".parse().unwrap();
+        assert_eq!(expr.scale(), Amnt!(0.000001));
+        for (expr, err) in [
+            ("", ParseUnitError::InvalidSyntax),
+            ("m/", ParseUnitError::InvalidSyntax),
+            ("(m/s", ParseUnitError::InvalidSyntax),
+            ("m/s)", ParseUnitError::InvalidSyntax),
+            ("m^", ParseUnitError::InvalidSyntax),
+            ("m^x", ParseUnitError::InvalidSyntax),
+            ("m2", ParseUnitError::InvalidSyntax),
+            ("m^128", ParseUnitError::InvalidSyntax),
+            ("m/parsec", ParseUnitError::UnknownUnit),
+            // prefixed units can't be prefixed
+            ("kkm", ParseUnitError::UnknownUnit),
+        ] {
+            assert_eq!(expr.parse::<UnitExpr>(), Err(err), "{expr}");
+        }
+        assert_eq!(<Force>::unit_from_expr("kg·m·s⁻²"), Ok(NEWTON));
+        assert_eq!(<Force>::unit_from_expr("µN"), Ok(MICRONEWTON));
+        assert_eq!(<Force>::unit_from_expr("g·mm·s⁻²"), Ok(MICRONEWTON));
+        assert_eq!(<Velocity>::unit_from_expr("m s^-1"), Ok(METER_PER_SECOND));
+        assert_eq!(<Velocity>::unit_from_expr("m·s⁻¹"), Ok(METER_PER_SECOND));
+        assert_eq!(<Velocity>::unit_from_expr("km/h"), Ok(KILOMETER_PER_HOUR));
+        assert_eq!(
+            <Velocity>::unit_from_expr("km·h⁻¹"),
+            Ok(KILOMETER_PER_HOUR)
+        );
+        assert_eq!(
+            <Acceleration>::unit_from_expr("m/s/s"),
+            Ok(METER_PER_SECOND_SQUARED)
+        );
+        assert_eq!(
+            <Velocity>::unit_from_expr("m/s²"),
+            Err(ParseUnitError::DimensionMismatch)
+        );
+        assert_eq!(
+            <Length>::unit_from_expr("Mm"),
+            Err(ParseUnitError::UnknownUnit)
+        );
+    }
+}
@@ -1,13 +1,423 @@
-#[cfg(target_pointer_width = "32")]
+#[cfg(feature = "fpdec")]
+pub use fpdec::{Dec, Decimal};
+
+#[cfg(feature = "fpdec")]
+pub type Amount = Decimal;
+
+#[cfg(all(not(feature = "fpdec"), target_pointer_width = "32"))]
 pub type Amount = f32;
 
-#[cfg(target_pointer_width = "64")]
+#[cfg(all(not(feature = "fpdec"), target_pointer_width = "64"))]
 pub type Amount = f64;
 
+#[cfg(feature = "fpdec")]
+pub const AMNT_ZERO: Amount = Decimal::ZERO;
+#[cfg(feature = "fpdec")]
+pub const AMNT_ONE: Amount = Decimal::ONE;
+
+#[cfg(not(feature = "fpdec"))]
 pub const AMNT_ZERO: Amount = 0.;
+#[cfg(not(feature = "fpdec"))]
 pub const AMNT_ONE: Amount = 1.;
 
+use core::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use crate::dimension::{self, HasDimension};
+
+/// The abstract type of values usable as the numerical part of a quantity.
+///
+/// Implementations are provided for `f32`, `f64`, `i32`, `i64` and - if
+/// feature `fpdec` is enabled - for `Decimal`.
+pub trait QuantityValue:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + HasDimension<Dim = dimension::One, Value = Self>
+{
+    /// The additive identity.
+    const ZERO: Self;
+    /// The multiplicative identity.
+    const ONE: Self;
+
+    /// Returns `amnt` converted to `Self`, rounded to the nearest value
+    /// representable by `Self`.
+    ///
+    /// This is used to apply the scales of units (which are given as
+    /// `Amount`) to values of type `Self`.
+    fn from_amount(amnt: Amount) -> Self;
+
+    /// Returns `self` multiplied by `factor`.
+    ///
+    /// The default implementation divides `self` by the inverse of `factor`
+    /// if `factor` is less than one, so that integral types do not lose all
+    /// of their precision when converted to a larger unit.
+    #[must_use]
+    fn mul_amount(self, factor: Amount) -> Self {
+        if factor >= AMNT_ONE {
+            self * Self::from_amount(factor)
+        } else {
+            self / Self::from_amount(AMNT_ONE / factor)
+        }
+    }
+
+    /// Returns `self` divided by `divisor`.
+    ///
+    /// The default implementation multiplies `self` by the inverse of
+    /// `divisor` if `divisor` is less than one, so that integral types do not
+    /// lose all of their precision when converted to a smaller unit.
+    #[must_use]
+    fn div_amount(self, divisor: Amount) -> Self {
+        if divisor >= AMNT_ONE {
+            self / Self::from_amount(divisor)
+        } else {
+            self * Self::from_amount(AMNT_ONE / divisor)
+        }
+    }
+
+    /// Returns the square root of `self`, rounded to the nearest value
+    /// representable by `Self`.
+    ///
+    /// # Panics
+    ///
+    /// Integral types and `Decimal` panic if `self` is negative, floats give
+    /// NaN.
+    #[must_use]
+    fn sqrt(self) -> Self;
+
+    /// Returns the cube root of `self`, rounded to the nearest value
+    /// representable by `Self`.
+    #[must_use]
+    fn cbrt(self) -> Self;
+}
+
+// Returns the square root (`n` == 2) or the cube root (`n` == 3) of `x`.
+// The root is computed by Newton's method, so that it is available without
+// `std`.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_wrap)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::integer_division)]
+fn f64_root(x: f64, n: i64) -> f64 {
+    if x.is_nan() || x.is_infinite() || x == 0. {
+        return x;
+    }
+    if x < 0. {
+        return if n == 2 { f64::NAN } else { -f64_root(-x, n) };
+    }
+    // start with the power of 2 given by dividing the binary exponent of `x`
+    // by `n`; the first step gives a value >= the root, the following steps
+    // decrease it until it doesn't change any more
+    let exp = ((x.to_bits() >> 52) & 0x7ff) as i64 - 1023;
+    let mut root = f64::from_bits(((exp / n + 1023) as u64) << 52);
+    let step = |y: f64| {
+        if n == 2 {
+            (y + x / y) / 2.
+        } else {
+            (2. * y + x / (y * y)) / 3.
+        }
+    };
+    root = step(root);
+    loop {
+        let next = step(root);
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+macro_rules! impl_quantity_value_for_float {
+    ($($t:ty),*) => {
+        $(
+        impl QuantityValue for $t {
+            const ZERO: Self = 0.;
+            const ONE: Self = 1.;
+
+            #[allow(clippy::useless_conversion)]
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline(always)]
+            fn from_amount(amnt: Amount) -> Self {
+                f64::from(amnt) as Self
+            }
+
+            #[inline(always)]
+            fn mul_amount(self, factor: Amount) -> Self {
+                self * Self::from_amount(factor)
+            }
+
+            #[inline(always)]
+            fn div_amount(self, divisor: Amount) -> Self {
+                self / Self::from_amount(divisor)
+            }
+
+            #[allow(clippy::useless_conversion)]
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline(always)]
+            fn sqrt(self) -> Self {
+                f64_root(f64::from(self), 2) as Self
+            }
+
+            #[allow(clippy::useless_conversion)]
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline(always)]
+            fn cbrt(self) -> Self {
+                f64_root(f64::from(self), 3) as Self
+            }
+        }
+        )*
+    };
+}
+
+impl_quantity_value_for_float!(f32, f64);
+
+// Rounds `$f` half away from zero and converts it to `$t` (`as` truncates
+// towards zero).
+macro_rules! round_f64 {
+    ($f:expr, $t:ty) => {{
+        let f: f64 = $f;
+        #[allow(clippy::cast_possible_truncation)]
+        if f < 0. {
+            (f - 0.5) as $t
+        } else {
+            (f + 0.5) as $t
+        }
+    }};
+}
+
+macro_rules! impl_quantity_value_for_int {
+    ($($t:ty),*) => {
+        $(
+        impl QuantityValue for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[allow(clippy::useless_conversion)]
+            #[inline(always)]
+            fn from_amount(amnt: Amount) -> Self {
+                round_f64!(f64::from(amnt), $t)
+            }
+
+            #[allow(clippy::useless_conversion)]
+            #[allow(clippy::cast_precision_loss)]
+            #[inline(always)]
+            fn mul_amount(self, factor: Amount) -> Self {
+                round_f64!(self as f64 * f64::from(factor), $t)
+            }
+
+            #[allow(clippy::useless_conversion)]
+            #[allow(clippy::cast_precision_loss)]
+            #[inline(always)]
+            fn div_amount(self, divisor: Amount) -> Self {
+                round_f64!(self as f64 / f64::from(divisor), $t)
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            #[inline(always)]
+            fn sqrt(self) -> Self {
+                assert!(self >= 0, "Can't take the square root of {}.", self);
+                round_f64!(f64_root(self as f64, 2), $t)
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            #[inline(always)]
+            fn cbrt(self) -> Self {
+                round_f64!(f64_root(self as f64, 3), $t)
+            }
+        }
+        )*
+    };
+}
+
+impl_quantity_value_for_int!(i32, i64);
+
+#[cfg(feature = "fpdec")]
+impl QuantityValue for Decimal {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+
+    #[inline(always)]
+    fn from_amount(amnt: Amount) -> Self {
+        amnt
+    }
+
+    #[inline(always)]
+    fn mul_amount(self, factor: Amount) -> Self {
+        self * factor
+    }
+
+    #[inline(always)]
+    fn div_amount(self, divisor: Amount) -> Self {
+        self / divisor
+    }
+
+    // The roots are computed as `f64`, so they are not exact beyond 15 or 16
+    // significant digits.
+    fn sqrt(self) -> Self {
+        assert!(self >= Self::ZERO, "Can't take the square root of {}.", self);
+        decimal_from_f64(f64_root(f64::from(self), 2))
+    }
+
+    fn cbrt(self) -> Self {
+        decimal_from_f64(f64_root(f64::from(self), 3))
+    }
+}
+
+#[cfg(feature = "fpdec")]
+fn decimal_from_f64(f: f64) -> Decimal {
+    Decimal::try_from(f)
+        .expect("Root of a Decimal must be representable as Decimal.")
+}
+
+/// Converts a numeric literal into an `Amount`, i.e. into a `Decimal` if
+/// feature `fpdec` is enabled, otherwise into a float.
+///
+/// The macro can be used in `const` contexts.
+#[cfg(feature = "fpdec")]
+#[macro_export]
+macro_rules! Amnt {
+    ($lit:literal) => {{
+        #[allow(unused_imports)]
+        use $crate::Decimal;
+        $crate::Dec!($lit)
+    }};
+}
+
+/// Converts a numeric literal into an `Amount`, i.e. into a `Decimal` if
+/// feature `fpdec` is enabled, otherwise into a float.
+///
+/// The macro can be used in `const` contexts.
+#[cfg(not(feature = "fpdec"))]
+#[macro_export]
+macro_rules! Amnt {
+    ($lit:literal) => {
+        $lit as $crate::Amount
+    };
+}
+
+// Implements `value * unit` and `value * qty` for the given value types.
+// Used in the code generated by macro `quantity`, because these impls can't be
+// generic over the value type.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_mul_value_unit_for {
+    ($qty:ident, $unit:ident, $($t:ty),*) => {
+        $(
+        impl ::core::ops::Mul<$unit> for $t {
+            type Output = $qty<$t>;
+            #[inline(always)]
+            fn mul(self, rhs: $unit) -> Self::Output {
+                $qty::new(self, rhs)
+            }
+        }
+        impl ::core::ops::Mul<$qty<$t>> for $t {
+            type Output = $qty<$t>;
+            #[inline(always)]
+            fn mul(self, rhs: $qty<$t>) -> Self::Output {
+                <Self::Output as $crate::Quantity>::new(
+                    self * $crate::Quantity::value(&rhs),
+                    $crate::Quantity::unit(&rhs),
+                )
+            }
+        }
+        )*
+    };
+}
+
+#[cfg(feature = "fpdec")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_mul_value_unit {
+    ($qty:ident, $unit:ident) => {
+        $crate::impl_mul_value_unit_for!(
+            $qty, $unit, f32, f64, i32, i64, $crate::Decimal
+        );
+    };
+}
+
+#[cfg(not(feature = "fpdec"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_mul_value_unit {
+    ($qty:ident, $unit:ident) => {
+        $crate::impl_mul_value_unit_for!($qty, $unit, f32, f64, i32, i64);
+    };
+}
+
+// Implements `Serialize` and `Deserialize` for the given quantity and unit
+// types, if feature `serde` is enabled. Used in the code generated by macro
+// `quantity`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serde {
+    ($qty:ident, $unit:ident) => {
+        impl $crate::__serde::Serialize for $unit {
+            #[inline(always)]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                $crate::serialization::serialize_unit(self, serializer)
+            }
+        }
+        impl<'de> $crate::__serde::Deserialize<'de> for $unit {
+            #[inline(always)]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                $crate::serialization::deserialize_unit(deserializer)
+            }
+        }
+        impl<V> $crate::__serde::Serialize for $qty<V>
+        where
+            V: $crate::QuantityValue + $crate::__serde::Serialize,
+        {
+            #[inline(always)]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                $crate::serialization::serialize_qty(self, serializer)
+            }
+        }
+        impl<'de, V> $crate::__serde::Deserialize<'de> for $qty<V>
+        where
+            V: $crate::QuantityValue
+                + $crate::__serde::Deserialize<'de>
+                + ::core::str::FromStr,
+        {
+            #[inline(always)]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                $crate::serialization::deserialize_qty(deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serde {
+    ($qty:ident, $unit:ident) => {};
+}
+
 #[doc(hidden)]
+#[cfg(not(feature = "fpdec"))]
 #[macro_export]
 macro_rules! assert_almost_eq {
     ($x:expr, $y:expr) => {